@@ -1,6 +1,6 @@
 use node_template_runtime::{
-	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig, Signature, SudoConfig,
-	SystemConfig, WASM_BINARY,
+	AccountId, AuraConfig, BalancesConfig, CouncilConfig, DemocracyConfig, GenesisConfig,
+	GrandpaConfig, Signature, SudoConfig, SystemConfig, WASM_BINARY,
 };
 use sc_service::ChainType;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -150,5 +150,12 @@ fn testnet_genesis(
 			// Assign network admin rights.
 			key: root_key,
 		},
+		council: CouncilConfig {
+			// The first two endowed accounts form the initial council.
+			members: endowed_accounts.iter().take(2).cloned().collect(),
+			phantom: Default::default(),
+		},
+		democracy: DemocracyConfig::default(),
+		treasury: Default::default(),
 	}
 }
@@ -7,12 +7,18 @@
 
 use std::sync::Arc;
 
-use node_template_runtime::{opaque::Block, AccountId, Balance, Index};
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result as RpcResult};
+use jsonrpc_derive::rpc;
+use node_template_runtime::{
+	opaque::Block, AccountId, Balance, FeeMultiplierApi as FeeMultiplierRuntimeApi, Index,
+	Multiplier,
+};
 pub use sc_rpc_api::DenyUnsafe;
 use sc_transaction_pool_api::TransactionPool;
 use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder;
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 
 /// Full client dependencies.
 pub struct FullDeps<C, P> {
@@ -32,6 +38,7 @@ where
 	C: Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+	C::Api: FeeMultiplierRuntimeApi<Block>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + 'static,
 {
@@ -45,6 +52,8 @@ where
 
 	io.extend_with(TransactionPaymentApi::to_delegate(TransactionPayment::new(client.clone())));
 
+	io.extend_with(FeeMultiplierApi::to_delegate(FeeMultiplier::new(client)));
+
 	// Extend this RPC with a custom API by using the following syntax.
 	// `YourRpcStruct` should have a reference to a client, which is needed
 	// to call into the runtime.
@@ -52,3 +61,39 @@ where
 
 	io
 }
+
+/// Fee RPC methods complementing those of `pallet_transaction_payment_rpc`.
+#[rpc]
+pub trait FeeMultiplierApi<BlockHash> {
+	/// The multiplier applied to the weight fee of transactions in the block after `at`, or
+	/// after the best block if `at` is not given.
+	#[rpc(name = "payment_nextFeeMultiplier")]
+	fn next_fee_multiplier(&self, at: Option<BlockHash>) -> RpcResult<Multiplier>;
+}
+
+/// Implements [`FeeMultiplierApi`] by calling into the runtime.
+pub struct FeeMultiplier<C> {
+	client: Arc<C>,
+}
+
+impl<C> FeeMultiplier<C> {
+	/// Create a new `FeeMultiplier` querying the runtime through `client`.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client }
+	}
+}
+
+impl<C> FeeMultiplierApi<<Block as BlockT>::Hash> for FeeMultiplier<C>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: FeeMultiplierRuntimeApi<Block>,
+{
+	fn next_fee_multiplier(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Multiplier> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client.runtime_api().next_fee_multiplier(&at).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(1),
+			message: "Unable to query the fee multiplier.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+}
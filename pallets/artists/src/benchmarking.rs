@@ -0,0 +1,21 @@
+//! Benchmarking setup for pallet-artists
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as Artists;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite};
+use frame_support::traits::EnsureOrigin;
+use sp_runtime::traits::StaticLookup;
+
+benchmarks! {
+	verify {
+		let artist: T::AccountId = account("artist", 0, 0);
+		let origin = T::VerifierOrigin::successful_origin();
+	}: _<T::Origin>(origin, T::Lookup::unlookup(artist.clone()))
+	verify {
+		assert!(Artists::<T>::is_verified(&artist));
+	}
+}
+
+impl_benchmark_test_suite!(Artists, crate::mock::new_test_ext(), crate::mock::Test);
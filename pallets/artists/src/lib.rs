@@ -0,0 +1,283 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// Verified artists and their quota of fee-less transactions.
+///
+/// Accounts verified by `VerifierOrigin` may submit up to `FreeCallsPerPeriod` of the calls
+/// selected by `FreeCalls` in every `QuotaPeriod` without paying transaction fees, so that new
+/// artists can mint before owning any tokens. The `WaiveFees` signed extension wraps the runtime's
+/// fee payment extension and skips it for such calls while quota is left. Once the quota of a
+/// period is used up, the calls are charged as usual, which keeps spamming them costly. Only one
+/// free call of an artist is valid in the transaction pool at a time, so that an artist without
+/// funds cannot queue more free calls than their quota allows.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+use codec::{Decode, Encode};
+use frame_support::{
+	dispatch::DispatchResult,
+	traits::{Filter, Get},
+};
+use sp_runtime::{
+	traits::{DispatchInfoOf, PostDispatchInfoOf, SignedExtension, UniqueSaturatedInto, Zero},
+	transaction_validity::{TransactionValidity, TransactionValidityError, ValidTransaction},
+};
+use sp_std::{fmt, marker::PhantomData, vec};
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{pallet_prelude::*, traits::StorageVersion};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::StaticLookup;
+
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(0);
+
+	/// Configure the pallet by specifying the parameters and types on which it depends.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The origin allowed to verify artists.
+		type VerifierOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The calls verified artists may submit without fees.
+		type FreeCalls: Filter<<Self as frame_system::Config>::Call>;
+
+		/// The length of the period the quota of free calls is granted for.
+		#[pallet::constant]
+		type QuotaPeriod: Get<Self::BlockNumber>;
+
+		/// The number of free calls a verified artist may submit per period.
+		#[pallet::constant]
+		type FreeCallsPerPeriod: Get<u32>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
+	pub struct Pallet<T>(_);
+
+	/// The verified artists.
+	#[pallet::storage]
+	pub type Verified<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, ()>;
+
+	/// The period a verified artist last submitted a free call in, and the number of free calls
+	/// submitted in it.
+	#[pallet::storage]
+	pub type FreeCallsUsed<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, (T::BlockNumber, u32), ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::metadata(T::AccountId = "AccountId")]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An artist was verified. [artist]
+		Verified(T::AccountId),
+		/// An artist is no longer verified. [artist]
+		Unverified(T::AccountId),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The account is already verified.
+		AlreadyVerified,
+		/// The account is not verified.
+		NotVerified,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Verify `artist`, granting them a quota of free calls.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn verify(
+			origin: OriginFor<T>,
+			artist: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			T::VerifierOrigin::ensure_origin(origin)?;
+			let artist = T::Lookup::lookup(artist)?;
+			ensure!(!Verified::<T>::contains_key(&artist), Error::<T>::AlreadyVerified);
+
+			Verified::<T>::insert(&artist, ());
+
+			Self::deposit_event(Event::Verified(artist));
+			Ok(())
+		}
+
+		/// Withdraw the verification of `artist`.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 2))]
+		pub fn unverify(
+			origin: OriginFor<T>,
+			artist: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			T::VerifierOrigin::ensure_origin(origin)?;
+			let artist = T::Lookup::lookup(artist)?;
+			ensure!(Verified::<T>::contains_key(&artist), Error::<T>::NotVerified);
+
+			Verified::<T>::remove(&artist);
+			FreeCallsUsed::<T>::remove(&artist);
+
+			Self::deposit_event(Event::Unverified(artist));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Whether `who` is a verified artist.
+		pub fn is_verified(who: &T::AccountId) -> bool {
+			Verified::<T>::contains_key(who)
+		}
+
+		/// The number of free calls `who` may still submit in the current period.
+		pub fn free_calls_left(who: &T::AccountId) -> u32 {
+			if !Self::is_verified(who) {
+				return 0
+			}
+			let (_, used) = Self::free_calls_used(who);
+			T::FreeCallsPerPeriod::get().saturating_sub(used)
+		}
+
+		/// The current quota period and the number of free calls `who` submitted in it.
+		pub(crate) fn free_calls_used(who: &T::AccountId) -> (T::BlockNumber, u32) {
+			let period = Self::current_period();
+			let (last, used) = FreeCallsUsed::<T>::get(who);
+			(period, if last == period { used } else { 0 })
+		}
+
+		/// The number of blocks until the quota is renewed, if ever.
+		pub(crate) fn blocks_left_in_period() -> Option<T::BlockNumber> {
+			let period = T::QuotaPeriod::get();
+			if period.is_zero() {
+				return None
+			}
+			Some(period - frame_system::Pallet::<T>::block_number() % period)
+		}
+
+		/// Count a free call of `who` against the quota of the current period.
+		pub(crate) fn use_free_call(who: &T::AccountId) {
+			let period = Self::current_period();
+			FreeCallsUsed::<T>::mutate(who, |(last, used)| {
+				if *last != period {
+					*last = period;
+					*used = 0;
+				}
+				*used = used.saturating_add(1);
+			});
+		}
+
+		/// The index of the current quota period.
+		fn current_period() -> T::BlockNumber {
+			let period = T::QuotaPeriod::get();
+			if period.is_zero() {
+				return Zero::zero()
+			}
+			frame_system::Pallet::<T>::block_number() / period
+		}
+	}
+}
+
+/// Wraps the fee payment extension `S`, skipping it for free calls of verified artists with quota
+/// left.
+#[derive(Encode, Decode, Clone, Eq, PartialEq)]
+pub struct WaiveFees<T: Config + Send + Sync, S>(pub S, #[codec(skip)] PhantomData<T>);
+
+impl<T: Config + Send + Sync, S> WaiveFees<T, S> {
+	/// Wrap the fee payment extension `inner`.
+	pub fn new(inner: S) -> Self {
+		Self(inner, PhantomData)
+	}
+
+	/// Whether `call` from `who` is free.
+	fn is_free(who: &T::AccountId, call: &<T as frame_system::Config>::Call) -> bool {
+		T::FreeCalls::filter(call) && Pallet::<T>::free_calls_left(who) > 0
+	}
+}
+
+impl<T: Config + Send + Sync, S: fmt::Debug> fmt::Debug for WaiveFees<T, S> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "WaiveFees({:?})", self.0)
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut fmt::Formatter) -> fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T, S> SignedExtension for WaiveFees<T, S>
+where
+	T: Config + Send + Sync,
+	S: SignedExtension<AccountId = T::AccountId, Call = <T as frame_system::Config>::Call>,
+{
+	const IDENTIFIER: &'static str = S::IDENTIFIER;
+	type AccountId = T::AccountId;
+	type Call = S::Call;
+	type AdditionalSigned = S::AdditionalSigned;
+	/// `None` if the fees were waived.
+	type Pre = Option<S::Pre>;
+
+	fn additional_signed(&self) -> Result<Self::AdditionalSigned, TransactionValidityError> {
+		self.0.additional_signed()
+	}
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> TransactionValidity {
+		if Self::is_free(who, call) {
+			// Every free call of the period provides its own tag, so a second one is only valid
+			// once the first has been included and counted against the quota.
+			let (period, used) = Pallet::<T>::free_calls_used(who);
+			let longevity = Pallet::<T>::blocks_left_in_period()
+				.map_or(ValidTransaction::default().longevity, |left| left.unique_saturated_into());
+			Ok(ValidTransaction {
+				provides: vec![(b"WaiveFees", who, period, used).encode()],
+				longevity,
+				..Default::default()
+			})
+		} else {
+			self.0.validate(who, call, info, len)
+		}
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		if Self::is_free(who, call) {
+			Pallet::<T>::use_free_call(who);
+			Ok(None)
+		} else {
+			self.0.pre_dispatch(who, call, info, len).map(Some)
+		}
+	}
+
+	fn post_dispatch(
+		pre: Self::Pre,
+		info: &DispatchInfoOf<Self::Call>,
+		post_info: &PostDispatchInfoOf<Self::Call>,
+		len: usize,
+		result: &DispatchResult,
+	) -> Result<(), TransactionValidityError> {
+		match pre {
+			Some(pre) => S::post_dispatch(pre, info, post_info, len, result),
+			None => Ok(()),
+		}
+	}
+}
@@ -0,0 +1,138 @@
+use crate as pallet_artists;
+use codec::{Decode, Encode};
+use frame_support::{parameter_types, traits::Filter};
+use frame_system::{self as system, EnsureRoot};
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, DispatchInfoOf, IdentityLookup, SignedExtension},
+	transaction_validity::{
+		InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
+	},
+};
+use std::cell::RefCell;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Artists: pallet_artists::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const SS58Prefix: u8 = 42;
+}
+
+impl system::Config for Test {
+	type BaseCallFilter = frame_support::traits::AllowAll;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = SS58Prefix;
+	type OnSetCode = ();
+}
+
+/// Remarks are the only free calls.
+pub struct FreeRemarks;
+impl Filter<Call> for FreeRemarks {
+	fn filter(call: &Call) -> bool {
+		matches!(call, Call::System(frame_system::Call::remark(..)))
+	}
+}
+
+parameter_types! {
+	pub const QuotaPeriod: u64 = 10;
+	pub const FreeCallsPerPeriod: u32 = 2;
+}
+
+impl pallet_artists::Config for Test {
+	type Event = Event;
+	type VerifierOrigin = EnsureRoot<u64>;
+	type FreeCalls = FreeRemarks;
+	type QuotaPeriod = QuotaPeriod;
+	type FreeCallsPerPeriod = FreeCallsPerPeriod;
+}
+
+thread_local! {
+	pub static CHARGED: RefCell<Vec<u64>> = RefCell::new(vec![]);
+}
+
+/// Charges every account but `99`, which cannot pay.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, Debug)]
+pub struct TestCharge;
+impl SignedExtension for TestCharge {
+	const IDENTIFIER: &'static str = "TestCharge";
+	type AccountId = u64;
+	type Call = Call;
+	type AdditionalSigned = ();
+	type Pre = ();
+
+	fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	fn validate(
+		&self,
+		who: &u64,
+		_: &Call,
+		_: &DispatchInfoOf<Call>,
+		_: usize,
+	) -> TransactionValidity {
+		if *who == 99 {
+			return Err(InvalidTransaction::Payment.into())
+		}
+		Ok(ValidTransaction::default())
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &u64,
+		_: &Call,
+		_: &DispatchInfoOf<Call>,
+		_: usize,
+	) -> Result<(), TransactionValidityError> {
+		if *who == 99 {
+			return Err(InvalidTransaction::Payment.into())
+		}
+		CHARGED.with(|charged| charged.borrow_mut().push(*who));
+		Ok(())
+	}
+}
+
+pub fn charged() -> Vec<u64> {
+	CHARGED.with(|charged| charged.borrow().clone())
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
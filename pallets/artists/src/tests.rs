@@ -0,0 +1,81 @@
+use crate::{mock::*, Error, WaiveFees};
+use frame_support::{assert_noop, assert_ok, weights::DispatchInfo};
+use sp_runtime::{
+	traits::SignedExtension, transaction_validity::TransactionValidity, DispatchError,
+};
+
+fn remark() -> Call {
+	Call::System(frame_system::Call::remark(vec![]))
+}
+
+fn submit(who: u64, call: &Call) -> bool {
+	WaiveFees::<Test, _>::new(TestCharge)
+		.pre_dispatch(&who, call, &DispatchInfo::default(), 0)
+		.is_ok()
+}
+
+fn validate(who: u64, call: &Call) -> TransactionValidity {
+	WaiveFees::<Test, _>::new(TestCharge).validate(&who, call, &DispatchInfo::default(), 0)
+}
+
+#[test]
+fn verifier_origin_manages_artists() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Artists::verify(Origin::signed(1), 1), DispatchError::BadOrigin);
+		assert_ok!(Artists::verify(Origin::root(), 1));
+		assert_noop!(Artists::verify(Origin::root(), 1), Error::<Test>::AlreadyVerified);
+		assert!(Artists::is_verified(&1));
+		assert_eq!(Artists::free_calls_left(&1), 2);
+
+		assert_ok!(Artists::unverify(Origin::root(), 1));
+		assert_noop!(Artists::unverify(Origin::root(), 1), Error::<Test>::NotVerified);
+		assert_eq!(Artists::free_calls_left(&1), 0);
+	});
+}
+
+#[test]
+fn verified_artists_submit_free_calls_within_their_quota() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Artists::verify(Origin::root(), 99));
+		let set_heap_pages = Call::System(frame_system::Call::set_heap_pages(1));
+
+		// Account 99 cannot pay fees, so only its free calls get through.
+		assert!(!submit(99, &set_heap_pages));
+		assert!(submit(99, &remark()));
+		assert!(submit(99, &remark()));
+		assert!(!submit(99, &remark()));
+		assert_eq!(Artists::free_calls_left(&99), 0);
+
+		// The quota is renewed in the next period.
+		System::set_block_number(10);
+		assert_eq!(Artists::free_calls_left(&99), 2);
+		assert!(submit(99, &remark()));
+		assert!(charged().is_empty());
+	});
+}
+
+#[test]
+fn others_pay_fees() {
+	new_test_ext().execute_with(|| {
+		assert!(submit(1, &remark()));
+		assert_eq!(charged(), vec![1]);
+	});
+}
+
+#[test]
+fn only_one_free_call_per_artist_is_valid_at_a_time() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Artists::verify(Origin::root(), 99));
+
+		// Pending free calls provide the same tag, so the pool keeps only one of them.
+		let first = validate(99, &remark()).unwrap();
+		assert_eq!(validate(99, &remark()).unwrap().provides, first.provides);
+		assert_eq!(first.longevity, 9);
+
+		assert!(submit(99, &remark()));
+		assert_ne!(validate(99, &remark()).unwrap().provides, first.provides);
+		assert!(submit(99, &remark()));
+		// Without quota left the call is validated by the fee payment, which account 99 fails.
+		assert!(validate(99, &remark()).is_err());
+	});
+}
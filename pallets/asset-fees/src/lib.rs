@@ -0,0 +1,302 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// Transaction fees paid in a platform asset.
+///
+/// Governance designates a fee asset, typically a stablecoin held in `pallet_assets`, together
+/// with the rate at which native fees convert into it. Accounts that opt in then pay their fees
+/// in that asset: `AssetFeeAdapter` wraps the runtime's native `OnChargeTransaction` and, for
+/// these accounts, transfers the converted fee to `FeeCollector` instead, refunding the excess
+/// once the actual weight of the call is known. Opting in is paid in the fee asset already, so
+/// that accounts without native funds can opt in. Without a fee asset, all fees are native.
+///
+/// Only sufficient assets, which accounts may hold without holding anything else, can become the
+/// fee asset, since accounts paying their fees in it need not hold native funds.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+use codec::{Decode, Encode};
+use frame_support::{
+	storage::migration::get_storage_value,
+	traits::{
+		tokens::fungibles::{Inspect, Transfer},
+		Get, IsSubType, PalletInfoAccess,
+	},
+	Blake2_128Concat, StorageHasher,
+};
+use pallet_transaction_payment::OnChargeTransaction;
+use sp_runtime::{
+	traits::{
+		DispatchInfoOf, PostDispatchInfoOf, Saturating, UniqueSaturatedFrom, UniqueSaturatedInto,
+		Zero,
+	},
+	transaction_validity::{InvalidTransaction, TransactionValidityError},
+	FixedPointNumber, FixedU128, RuntimeDebug,
+};
+use sp_std::marker::PhantomData;
+
+pub type AssetIdOf<T> =
+	<<T as Config>::Assets as Inspect<<T as frame_system::Config>::AccountId>>::AssetId;
+pub type AssetBalanceOf<T> =
+	<<T as Config>::Assets as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// Tells whether an asset is sufficient, i.e. whether accounts may hold it without holding
+/// anything else.
+pub trait AssetSufficiency<AssetId> {
+	/// Whether `asset` exists and is sufficient.
+	fn is_sufficient(asset: &AssetId) -> bool;
+}
+
+/// The leading fields of `pallet_assets::AssetDetails`, up to `is_sufficient`.
+#[derive(Decode)]
+struct AssetDetailsPrefix<AccountId, Balance, DepositBalance> {
+	_owner: AccountId,
+	_issuer: AccountId,
+	_admin: AccountId,
+	_freezer: AccountId,
+	_supply: Balance,
+	_deposit: DepositBalance,
+	_min_balance: Balance,
+	is_sufficient: bool,
+}
+
+/// Reads the sufficiency of assets held in the `pallet_assets` instance `P`, which keeps its
+/// asset details private, from its `Asset` storage.
+pub struct PalletAssetsSufficiency<P, AccountId, Balance, DepositBalance>(
+	PhantomData<(P, AccountId, Balance, DepositBalance)>,
+);
+
+impl<P, AccountId, Balance, DepositBalance, AssetId> AssetSufficiency<AssetId>
+	for PalletAssetsSufficiency<P, AccountId, Balance, DepositBalance>
+where
+	P: PalletInfoAccess,
+	AccountId: Decode,
+	Balance: Decode,
+	DepositBalance: Decode,
+	AssetId: Encode,
+{
+	fn is_sufficient(asset: &AssetId) -> bool {
+		get_storage_value::<AssetDetailsPrefix<AccountId, Balance, DepositBalance>>(
+			P::name().as_bytes(),
+			b"Asset",
+			&Blake2_128Concat::hash(&asset.encode()),
+		)
+		.map_or(false, |details| details.is_sufficient)
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{dispatch::DispatchResult, pallet_prelude::*, traits::StorageVersion};
+	use frame_system::pallet_prelude::*;
+
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(0);
+
+	/// Configure the pallet by specifying the parameters and types on which it depends.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The assets fees may be paid in.
+		type Assets: Transfer<Self::AccountId>;
+
+		/// Tells which assets are sufficient and so may become the fee asset.
+		type Sufficiency: AssetSufficiency<AssetIdOf<Self>>;
+
+		/// The origin allowed to set the fee asset and its conversion rate.
+		type FeeAssetOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The account fees paid in the fee asset are transferred to. It must hold at least the
+		/// minimum balance of an asset for it to become the fee asset, so that fees below the
+		/// minimum balance can be paid to it.
+		type FeeCollector: Get<Self::AccountId>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
+	pub struct Pallet<T>(_);
+
+	/// The asset fees may be paid in, and the amount of it one native unit converts into.
+	#[pallet::storage]
+	#[pallet::getter(fn fee_asset)]
+	pub type FeeAsset<T: Config> = StorageValue<_, (AssetIdOf<T>, FixedU128)>;
+
+	/// The accounts paying their fees in the fee asset.
+	#[pallet::storage]
+	pub type PayInAsset<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, ()>;
+
+	#[pallet::event]
+	#[pallet::metadata(T::AccountId = "AccountId", AssetIdOf<T> = "AssetId")]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The fee asset and its conversion rate were set. [asset, rate]
+		FeeAssetSet(AssetIdOf<T>, FixedU128),
+		/// Fees can only be paid natively again.
+		FeeAssetRemoved,
+		/// An account chose how to pay its fees. [who, in_asset]
+		FeePaymentSet(T::AccountId, bool),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The conversion rate is zero, which would make transactions free.
+		ZeroRate,
+		/// `FeeCollector` does not hold the minimum balance of the asset.
+		FeeCollectorNotFunded,
+		/// The asset is not sufficient, so accounts without native funds cannot hold it.
+		InsufficientAsset,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set the asset fees may be paid in and the amount of it one native unit converts into,
+		/// or only accept native fees with `None`. The asset must be sufficient and `FeeCollector`
+		/// must hold at least its minimum balance.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 1))]
+		pub fn set_fee_asset(
+			origin: OriginFor<T>,
+			fee_asset: Option<(AssetIdOf<T>, FixedU128)>,
+		) -> DispatchResult {
+			T::FeeAssetOrigin::ensure_origin(origin)?;
+
+			match fee_asset {
+				Some((asset, rate)) => {
+					ensure!(!rate.is_zero(), Error::<T>::ZeroRate);
+					ensure!(T::Sufficiency::is_sufficient(&asset), Error::<T>::InsufficientAsset);
+					ensure!(
+						T::Assets::balance(asset, &T::FeeCollector::get()) >=
+							T::Assets::minimum_balance(asset),
+						Error::<T>::FeeCollectorNotFunded
+					);
+					FeeAsset::<T>::put((asset, rate));
+					Self::deposit_event(Event::FeeAssetSet(asset, rate));
+				},
+				None => {
+					FeeAsset::<T>::kill();
+					Self::deposit_event(Event::FeeAssetRemoved);
+				},
+			}
+			Ok(())
+		}
+
+		/// Choose whether the caller pays its fees in the fee asset or natively. Opting in is paid
+		/// in the fee asset.
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		pub fn set_fee_payment(origin: OriginFor<T>, in_asset: bool) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			if in_asset {
+				PayInAsset::<T>::insert(&who, ());
+			} else {
+				PayInAsset::<T>::remove(&who);
+			}
+
+			Self::deposit_event(Event::FeePaymentSet(who, in_asset));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The fee asset and conversion rate `who` pays fees with, if not native.
+		pub fn fee_asset_of(who: &T::AccountId) -> Option<(AssetIdOf<T>, FixedU128)> {
+			if PayInAsset::<T>::contains_key(who) {
+				Self::fee_asset()
+			} else {
+				None
+			}
+		}
+
+		/// Convert the native `amount` into the fee asset at `rate`.
+		pub fn to_asset<B: UniqueSaturatedInto<u128>>(
+			rate: FixedU128,
+			amount: B,
+		) -> AssetBalanceOf<T> {
+			let amount: u128 = amount.unique_saturated_into();
+			AssetBalanceOf::<T>::unique_saturated_from(rate.saturating_mul_int(amount))
+		}
+	}
+}
+
+/// What was withdrawn for the fee of a transaction.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum FeeLiquidity<Native, AssetId, AssetBalance> {
+	/// The liquidity withdrawn by the native fee payment.
+	Native(Native),
+	/// The amount paid in the fee asset, and the rate it was converted at.
+	Asset(AssetId, FixedU128, AssetBalance),
+}
+
+impl<Native: Default, AssetId, AssetBalance> Default
+	for FeeLiquidity<Native, AssetId, AssetBalance>
+{
+	fn default() -> Self {
+		Self::Native(Default::default())
+	}
+}
+
+/// Wraps the native fee payment `N`, charging accounts that opted in, or are opting in, with the
+/// fee asset instead.
+pub struct AssetFeeAdapter<T, N>(PhantomData<(T, N)>);
+
+impl<T, N> OnChargeTransaction<T> for AssetFeeAdapter<T, N>
+where
+	T: Config + pallet_transaction_payment::Config,
+	<T as frame_system::Config>::Call: IsSubType<Call<T>>,
+	N: OnChargeTransaction<T>,
+{
+	type Balance = N::Balance;
+	type LiquidityInfo = FeeLiquidity<N::LiquidityInfo, AssetIdOf<T>, AssetBalanceOf<T>>;
+
+	fn withdraw_fee(
+		who: &T::AccountId,
+		call: &<T as frame_system::Config>::Call,
+		info: &DispatchInfoOf<<T as frame_system::Config>::Call>,
+		fee: Self::Balance,
+		tip: Self::Balance,
+	) -> Result<Self::LiquidityInfo, TransactionValidityError> {
+		let fee_asset = Pallet::<T>::fee_asset_of(who).or_else(|| match call.is_sub_type() {
+			Some(Call::set_fee_payment(true)) => Pallet::<T>::fee_asset(),
+			_ => None,
+		});
+		let (asset, rate) = match fee_asset {
+			Some(fee_asset) => fee_asset,
+			None => return N::withdraw_fee(who, call, info, fee, tip).map(FeeLiquidity::Native),
+		};
+		let amount = Pallet::<T>::to_asset(rate, fee);
+		if !amount.is_zero() {
+			T::Assets::transfer(asset, who, &T::FeeCollector::get(), amount, true)
+				.map_err(|_| InvalidTransaction::Payment)?;
+		}
+		Ok(FeeLiquidity::Asset(asset, rate, amount))
+	}
+
+	fn correct_and_deposit_fee(
+		who: &T::AccountId,
+		info: &DispatchInfoOf<<T as frame_system::Config>::Call>,
+		post_info: &PostDispatchInfoOf<<T as frame_system::Config>::Call>,
+		corrected_fee: Self::Balance,
+		tip: Self::Balance,
+		already_withdrawn: Self::LiquidityInfo,
+	) -> Result<(), TransactionValidityError> {
+		match already_withdrawn {
+			FeeLiquidity::Native(liquidity) =>
+				N::correct_and_deposit_fee(who, info, post_info, corrected_fee, tip, liquidity),
+			FeeLiquidity::Asset(asset, rate, paid) => {
+				let refund = paid.saturating_sub(Pallet::<T>::to_asset(rate, corrected_fee));
+				if !refund.is_zero() {
+					// Like the native payment, a refund that cannot be made is not an error.
+					let _ = T::Assets::transfer(asset, &T::FeeCollector::get(), who, refund, false);
+				}
+				Ok(())
+			},
+		}
+	}
+}
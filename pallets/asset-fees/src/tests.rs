@@ -0,0 +1,116 @@
+use crate::{mock::*, Error};
+use frame_support::{
+	assert_noop, assert_ok,
+	weights::{DispatchInfo, PostDispatchInfo},
+};
+use pallet_transaction_payment::OnChargeTransaction;
+use sp_runtime::{traits::BadOrigin, FixedPointNumber, FixedU128};
+
+type Adapter = <Test as pallet_transaction_payment::Config>::OnChargeTransaction;
+
+/// Create asset 1 as fee asset at two units per native unit, and give account 2 some of it.
+fn set_up_fee_asset() {
+	assert_ok!(Assets::force_create(Origin::root(), 1, 1, true, 1));
+	assert_ok!(Assets::mint(Origin::signed(1), 1, 2, 1000));
+	assert_ok!(Assets::mint(Origin::signed(1), 1, 99, 1));
+	assert_ok!(AssetFees::set_fee_asset(
+		Origin::root(),
+		Some((1, FixedU128::saturating_from_integer(2)))
+	));
+}
+
+fn remark() -> Call {
+	Call::System(frame_system::Call::remark(vec![]))
+}
+
+#[test]
+fn governance_sets_the_fee_asset() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(AssetFees::set_fee_asset(Origin::signed(1), None), BadOrigin);
+		assert_noop!(
+			AssetFees::set_fee_asset(Origin::root(), Some((1, FixedU128::zero()))),
+			Error::<Test>::ZeroRate
+		);
+
+		// Accounts without native funds cannot hold an insufficient asset.
+		assert_ok!(Assets::force_create(Origin::root(), 2, 1, false, 1));
+		assert_noop!(
+			AssetFees::set_fee_asset(
+				Origin::root(),
+				Some((2, FixedU128::saturating_from_integer(1)))
+			),
+			Error::<Test>::InsufficientAsset
+		);
+		assert_noop!(
+			AssetFees::set_fee_asset(
+				Origin::root(),
+				Some((3, FixedU128::saturating_from_integer(1)))
+			),
+			Error::<Test>::InsufficientAsset
+		);
+
+		// The fee collector must hold the minimum balance of the fee asset.
+		assert_ok!(Assets::force_create(Origin::root(), 1, 1, true, 1));
+		let fee_asset = Some((1, FixedU128::saturating_from_integer(2)));
+		assert_noop!(
+			AssetFees::set_fee_asset(Origin::root(), fee_asset),
+			Error::<Test>::FeeCollectorNotFunded
+		);
+
+		assert_ok!(Assets::mint(Origin::signed(1), 1, 99, 1));
+		assert_ok!(AssetFees::set_fee_asset(Origin::root(), fee_asset));
+		assert_eq!(AssetFees::fee_asset(), fee_asset);
+		assert_ok!(AssetFees::set_fee_asset(Origin::root(), None));
+		assert_eq!(AssetFees::fee_asset(), None);
+	});
+}
+
+#[test]
+fn fees_are_paid_in_the_fee_asset_when_opted_in() {
+	new_test_ext().execute_with(|| {
+		set_up_fee_asset();
+		assert_ok!(AssetFees::set_fee_payment(Origin::signed(2), true));
+		let info = DispatchInfo::default();
+
+		let paid = Adapter::withdraw_fee(&2, &remark(), &info, 10, 0).unwrap();
+		assert_eq!(Assets::balance(1, 2), 980);
+		assert_eq!(Assets::balance(1, 99), 21);
+
+		// The unused part of the fee is refunded in the fee asset.
+		let post_info = PostDispatchInfo { actual_weight: None, pays_fee: Default::default() };
+		assert_ok!(Adapter::correct_and_deposit_fee(&2, &info, &post_info, 6, 0, paid));
+		assert_eq!(Assets::balance(1, 2), 988);
+		assert_eq!(Assets::balance(1, 99), 13);
+		assert_eq!(Balances::free_balance(2), 1000);
+	});
+}
+
+#[test]
+fn fees_are_paid_natively_otherwise() {
+	new_test_ext().execute_with(|| {
+		let info = DispatchInfo::default();
+		// Opted in, but there is no fee asset.
+		assert_ok!(AssetFees::set_fee_payment(Origin::signed(2), true));
+		assert_ok!(Adapter::withdraw_fee(&2, &remark(), &info, 10, 0));
+		assert_eq!(Balances::free_balance(2), 990);
+
+		set_up_fee_asset();
+		assert_ok!(AssetFees::set_fee_payment(Origin::signed(2), false));
+		assert_ok!(Adapter::withdraw_fee(&2, &remark(), &info, 10, 0));
+		assert_eq!(Balances::free_balance(2), 980);
+		assert_eq!(Assets::balance(1, 2), 1000);
+	});
+}
+
+#[test]
+fn opting_in_is_paid_in_the_fee_asset() {
+	new_test_ext().execute_with(|| {
+		set_up_fee_asset();
+		let opt_in = Call::AssetFees(crate::Call::set_fee_payment(true));
+		let info = DispatchInfo::default();
+
+		assert_ok!(Adapter::withdraw_fee(&2, &opt_in, &info, 10, 0));
+		assert_eq!(Assets::balance(1, 2), 980);
+		assert_eq!(Balances::free_balance(2), 1000);
+	});
+}
@@ -0,0 +1,31 @@
+//! Benchmarking setup for pallet-badges
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as BadgesPallet;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_system::RawOrigin;
+use sp_runtime::traits::StaticLookup;
+
+benchmarks! {
+	issue {
+		let caller: T::AccountId = whitelisted_caller();
+		Issuers::<T>::insert(&caller, ());
+		let holder: T::AccountId = account("holder", 0, 0);
+	}: _(RawOrigin::Signed(caller), T::Lookup::unlookup(holder.clone()), BadgeKind::Attendance, Default::default())
+	verify {
+		assert!(BadgesOf::<T>::contains_key(holder, 0));
+	}
+
+	revoke {
+		let caller: T::AccountId = whitelisted_caller();
+		let holder: T::AccountId = account("holder", 0, 0);
+		Pallet::<T>::do_issue(caller.clone(), holder, BadgeKind::Achievement, Default::default())?;
+	}: _(RawOrigin::Signed(caller), 0)
+	verify {
+		assert!(!Badges::<T>::contains_key(0));
+	}
+}
+
+impl_benchmark_test_suite!(BadgesPallet, crate::mock::new_test_ext(), crate::mock::Test);
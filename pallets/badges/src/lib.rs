@@ -0,0 +1,213 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// Soulbound achievement and attendance badges.
+///
+/// Badges are issued to an account by an authorized issuer and stay bound to that account: the
+/// pallet offers no way to transfer them, and other pallets can only issue them through
+/// `Pallet::do_issue`. The issuer of a badge can revoke it again.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+use codec::{Decode, Encode};
+use sp_runtime::RuntimeDebug;
+
+/// Identifier of a badge.
+pub type BadgeId = u64;
+
+/// What a badge is awarded for.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum BadgeKind {
+	/// Proof of attendance, e.g. of an exhibition.
+	Attendance,
+	/// An achievement, e.g. a first sale or a residency.
+	Achievement,
+}
+
+/// Details of an issued badge.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Badge<AccountId, Hash, BlockNumber> {
+	/// The account the badge is bound to.
+	pub holder: AccountId,
+	/// The account that issued the badge.
+	pub issuer: AccountId,
+	/// What the badge is awarded for.
+	pub kind: BadgeKind,
+	/// Hash of the off-chain description of the badge.
+	pub metadata: Hash,
+	/// The block the badge was issued at.
+	pub issued_at: BlockNumber,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{dispatch::DispatchResult, pallet_prelude::*, traits::StorageVersion};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::StaticLookup;
+	use sp_std::prelude::*;
+
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(0);
+
+	/// Configure the pallet by specifying the parameters and types on which it depends.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The origin allowed to authorize and remove issuers.
+		type IssuerOrigin: EnsureOrigin<Self::Origin>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
+	pub struct Pallet<T>(_);
+
+	/// The accounts allowed to issue badges.
+	#[pallet::storage]
+	pub type Issuers<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, ()>;
+
+	/// The id the next issued badge will get.
+	#[pallet::storage]
+	#[pallet::getter(fn next_badge_id)]
+	pub type NextBadgeId<T> = StorageValue<_, BadgeId, ValueQuery>;
+
+	/// Details of every badge.
+	#[pallet::storage]
+	#[pallet::getter(fn badge)]
+	pub type Badges<T: Config> =
+		StorageMap<_, Twox64Concat, BadgeId, Badge<T::AccountId, T::Hash, T::BlockNumber>>;
+
+	/// The badges held by every account.
+	#[pallet::storage]
+	pub type BadgesOf<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Twox64Concat, BadgeId, ()>;
+
+	#[pallet::event]
+	#[pallet::metadata(T::AccountId = "AccountId")]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An account was authorized to issue badges. [issuer]
+		IssuerAdded(T::AccountId),
+		/// An account may no longer issue badges. [issuer]
+		IssuerRemoved(T::AccountId),
+		/// A badge was issued. [badge, issuer, holder]
+		Issued(BadgeId, T::AccountId, T::AccountId),
+		/// A badge was revoked. [badge, holder]
+		Revoked(BadgeId, T::AccountId),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The account is not an authorized issuer.
+		NotIssuer,
+		/// The badge does not exist.
+		UnknownBadge,
+		/// Only the issuer of a badge may revoke it.
+		NotBadgeIssuer,
+		/// An identifier overflowed.
+		Overflow,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Authorize `issuer` to issue badges.
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		pub fn add_issuer(
+			origin: OriginFor<T>,
+			issuer: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			T::IssuerOrigin::ensure_origin(origin)?;
+			let issuer = T::Lookup::lookup(issuer)?;
+
+			Issuers::<T>::insert(&issuer, ());
+
+			Self::deposit_event(Event::IssuerAdded(issuer));
+			Ok(())
+		}
+
+		/// Withdraw the authorization of `issuer`. Badges it issued stay valid.
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		pub fn remove_issuer(
+			origin: OriginFor<T>,
+			issuer: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			T::IssuerOrigin::ensure_origin(origin)?;
+			let issuer = T::Lookup::lookup(issuer)?;
+			ensure!(Issuers::<T>::contains_key(&issuer), Error::<T>::NotIssuer);
+
+			Issuers::<T>::remove(&issuer);
+
+			Self::deposit_event(Event::IssuerRemoved(issuer));
+			Ok(())
+		}
+
+		/// Issue a badge to `holder`. The caller must be an authorized issuer.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 3))]
+		pub fn issue(
+			origin: OriginFor<T>,
+			holder: <T::Lookup as StaticLookup>::Source,
+			kind: BadgeKind,
+			metadata: T::Hash,
+		) -> DispatchResult {
+			let issuer = ensure_signed(origin)?;
+			let holder = T::Lookup::lookup(holder)?;
+			ensure!(Issuers::<T>::contains_key(&issuer), Error::<T>::NotIssuer);
+
+			Self::do_issue(issuer, holder, kind, metadata).map(|_| ())
+		}
+
+		/// Revoke a badge. Only the account that issued it may do so.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 2))]
+		pub fn revoke(origin: OriginFor<T>, badge: BadgeId) -> DispatchResult {
+			let issuer = ensure_signed(origin)?;
+			let details = Self::badge(badge).ok_or(Error::<T>::UnknownBadge)?;
+			ensure!(details.issuer == issuer, Error::<T>::NotBadgeIssuer);
+
+			Badges::<T>::remove(badge);
+			BadgesOf::<T>::remove(&details.holder, badge);
+
+			Self::deposit_event(Event::Revoked(badge, details.holder));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Issue a badge to `holder` on behalf of `issuer` without checking that `issuer` is
+		/// authorized, returning the id of the new badge.
+		pub fn do_issue(
+			issuer: T::AccountId,
+			holder: T::AccountId,
+			kind: BadgeKind,
+			metadata: T::Hash,
+		) -> Result<BadgeId, DispatchError> {
+			let badge = Self::next_badge_id();
+			let next = badge.checked_add(1).ok_or(Error::<T>::Overflow)?;
+			let issued_at = frame_system::Pallet::<T>::block_number();
+
+			Badges::<T>::insert(
+				badge,
+				Badge { holder: holder.clone(), issuer: issuer.clone(), kind, metadata, issued_at },
+			);
+			BadgesOf::<T>::insert(&holder, badge, ());
+			NextBadgeId::<T>::put(next);
+
+			Self::deposit_event(Event::Issued(badge, issuer, holder));
+			Ok(badge)
+		}
+
+		/// The ids of all badges held by `holder`.
+		pub fn badges_of(holder: &T::AccountId) -> Vec<BadgeId> {
+			BadgesOf::<T>::iter_prefix(holder).map(|(badge, _)| badge).collect()
+		}
+	}
+}
@@ -0,0 +1,53 @@
+use crate::{mock::*, Badge, BadgeKind, Error};
+use frame_support::{assert_noop, assert_ok};
+use sp_core::H256;
+use sp_runtime::DispatchError;
+
+#[test]
+fn only_authorized_issuers_can_issue() {
+	new_test_ext().execute_with(|| {
+		let metadata = H256::repeat_byte(1);
+		assert_noop!(
+			Badges::issue(Origin::signed(1), 2, BadgeKind::Attendance, metadata),
+			Error::<Test>::NotIssuer
+		);
+		assert_noop!(Badges::add_issuer(Origin::signed(1), 1), DispatchError::BadOrigin);
+
+		assert_ok!(Badges::add_issuer(Origin::root(), 1));
+		assert_ok!(Badges::issue(Origin::signed(1), 2, BadgeKind::Attendance, metadata));
+		assert_eq!(
+			Badges::badge(0),
+			Some(Badge {
+				holder: 2,
+				issuer: 1,
+				kind: BadgeKind::Attendance,
+				metadata,
+				issued_at: 1
+			})
+		);
+		assert_eq!(Badges::badges_of(&2), vec![0]);
+
+		assert_ok!(Badges::remove_issuer(Origin::root(), 1));
+		assert_noop!(
+			Badges::issue(Origin::signed(1), 2, BadgeKind::Achievement, metadata),
+			Error::<Test>::NotIssuer
+		);
+		// Badges outlive the authorization of their issuer.
+		assert!(Badges::badge(0).is_some());
+	});
+}
+
+#[test]
+fn issuers_can_revoke_their_badges() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Badges::add_issuer(Origin::root(), 1));
+		assert_ok!(Badges::add_issuer(Origin::root(), 3));
+		assert_ok!(Badges::issue(Origin::signed(1), 2, BadgeKind::Achievement, H256::zero()));
+
+		assert_noop!(Badges::revoke(Origin::signed(3), 0), Error::<Test>::NotBadgeIssuer);
+		assert_ok!(Badges::revoke(Origin::signed(1), 0));
+		assert_eq!(Badges::badge(0), None);
+		assert!(Badges::badges_of(&2).is_empty());
+		assert_noop!(Badges::revoke(Origin::signed(1), 0), Error::<Test>::UnknownBadge);
+	});
+}
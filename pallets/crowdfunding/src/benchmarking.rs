@@ -0,0 +1,50 @@
+//! Benchmarking setup for pallet-crowdfunding
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as Crowdfunding;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::traits::{Currency, Get};
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Bounded, StaticLookup};
+use sp_std::prelude::*;
+
+fn campaign_with_contribution<T: Config>(contributor: &T::AccountId) -> BalanceOf<T> {
+	let creator: T::AccountId = account("creator", 0, 0);
+	let amount = T::Currency::minimum_balance();
+	T::Currency::make_free_balance_be(&creator, amount);
+	T::Currency::make_free_balance_be(contributor, BalanceOf::<T>::max_value());
+	Crowdfunding::<T>::create_campaign(
+		RawOrigin::Signed(creator).into(),
+		amount,
+		T::MinDuration::get(),
+		vec![],
+	)
+	.expect("campaign is valid; qed");
+	Crowdfunding::<T>::contribute(RawOrigin::Signed(contributor.clone()).into(), 0, amount)
+		.expect("contributor is funded; qed");
+	amount
+}
+
+benchmarks! {
+	contribute {
+		let caller: T::AccountId = whitelisted_caller();
+		let amount = campaign_with_contribution::<T>(&account("contributor", 0, 0));
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+	}: _(RawOrigin::Signed(caller.clone()), 0, amount)
+	verify {
+		assert_eq!(Contributions::<T>::get(0, caller), amount);
+	}
+
+	settle {
+		let caller: T::AccountId = whitelisted_caller();
+		campaign_with_contribution::<T>(&caller);
+		frame_system::Pallet::<T>::set_block_number(T::MaxDuration::get());
+	}: _(RawOrigin::Signed(caller.clone()), 0, T::Lookup::unlookup(caller.clone()))
+	verify {
+		assert!(!Contributions::<T>::contains_key(0, caller));
+	}
+}
+
+impl_benchmark_test_suite!(Crowdfunding, crate::mock::new_test_ext(), crate::mock::Test);
@@ -0,0 +1,423 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// Crowdfunding campaigns for art projects.
+///
+/// An artist opens a campaign with a funding goal, a deadline and optional reward tiers.
+/// Contributions are reserved from the contributors' accounts while the campaign runs. Once the
+/// deadline has passed, each contribution is settled individually: if the goal was met the
+/// reserved funds go to the artist and the contributor receives the reward of the best tier their
+/// contribution qualifies for, otherwise the funds are unreserved again. Ended campaigns are
+/// settled from `on_initialize`, at most `MaxSettlementsPerBlock` contributions per block; anyone
+/// may also settle a contribution right away with `settle`.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+use codec::{Decode, Encode};
+use frame_support::dispatch::{DispatchResult, Parameter};
+use sp_runtime::RuntimeDebug;
+
+/// Identifier of a crowdfunding campaign.
+pub type CampaignId = u32;
+
+/// Mints the reward artworks of successful campaigns.
+pub trait RewardMinter<AccountId> {
+	/// Describes which artwork is minted as a reward.
+	type Reward: Parameter;
+
+	/// Whether `creator` is allowed to hand out `reward`.
+	fn can_mint(creator: &AccountId, reward: &Self::Reward) -> bool;
+
+	/// Make sure `reward` stays mintable once a campaign hands it out, e.g. by freezing the
+	/// artwork it is minted from.
+	fn freeze(reward: &Self::Reward);
+
+	/// Mint `reward` on behalf of `creator` to `owner`.
+	fn mint(creator: &AccountId, reward: &Self::Reward, owner: &AccountId) -> DispatchResult;
+}
+
+impl<AccountId> RewardMinter<AccountId> for () {
+	type Reward = ();
+
+	fn can_mint(_: &AccountId, _: &()) -> bool {
+		true
+	}
+
+	fn freeze(_: &()) {}
+
+	fn mint(_: &AccountId, _: &(), _: &AccountId) -> DispatchResult {
+		Ok(())
+	}
+}
+
+/// Details of a crowdfunding campaign.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Campaign<AccountId, Balance, BlockNumber> {
+	/// The artist receiving the funds.
+	pub creator: AccountId,
+	/// The amount that must be raised for the campaign to succeed.
+	pub goal: Balance,
+	/// The amount raised so far.
+	pub raised: Balance,
+	/// The block from which no contributions are accepted and contributions can be settled.
+	pub deadline: BlockNumber,
+}
+
+/// A reward handed out to every contributor of at least `min_contribution`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct RewardTier<Balance, Reward> {
+	/// The smallest contribution that qualifies for this tier.
+	pub min_contribution: Balance,
+	/// The reward minted to qualifying contributors.
+	pub reward: Reward,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{BalanceStatus, Currency, ReservableCurrency, StorageVersion},
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::{Saturating, StaticLookup, Zero};
+	use sp_std::{convert::TryInto, prelude::*};
+
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub type RewardOf<T> =
+		<<T as Config>::Rewards as RewardMinter<<T as frame_system::Config>::AccountId>>::Reward;
+
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(0);
+
+	/// Configure the pallet by specifying the parameters and types on which it depends.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency contributions are made in.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// Mints the rewards of successful campaigns.
+		type Rewards: RewardMinter<Self::AccountId>;
+
+		/// The shortest duration of a campaign.
+		#[pallet::constant]
+		type MinDuration: Get<Self::BlockNumber>;
+
+		/// The longest duration of a campaign.
+		#[pallet::constant]
+		type MaxDuration: Get<Self::BlockNumber>;
+
+		/// The maximum number of reward tiers of a campaign.
+		#[pallet::constant]
+		type MaxRewardTiers: Get<u32>;
+
+		/// The maximum number of contributions settled in `on_initialize` per block.
+		#[pallet::constant]
+		type MaxSettlementsPerBlock: Get<u32>;
+
+		/// The maximum number of campaigns that can end in the same block.
+		#[pallet::constant]
+		type MaxCampaignsEndingPerBlock: Get<u32>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
+	pub struct Pallet<T>(_);
+
+	/// The id the next created campaign will get.
+	#[pallet::storage]
+	#[pallet::getter(fn next_campaign_id)]
+	pub type NextCampaignId<T> = StorageValue<_, CampaignId, ValueQuery>;
+
+	/// Details of every campaign.
+	#[pallet::storage]
+	#[pallet::getter(fn campaign)]
+	pub type Campaigns<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		CampaignId,
+		Campaign<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+	>;
+
+	/// The reward tiers of every campaign, ordered by ascending `min_contribution`.
+	#[pallet::storage]
+	#[pallet::getter(fn reward_tiers)]
+	pub type RewardTiers<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		CampaignId,
+		BoundedVec<RewardTier<BalanceOf<T>, RewardOf<T>>, T::MaxRewardTiers>,
+		ValueQuery,
+	>;
+
+	/// Unsettled contributions, keyed by campaign and contributor.
+	#[pallet::storage]
+	#[pallet::getter(fn contribution)]
+	pub type Contributions<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		CampaignId,
+		Blake2_128Concat,
+		T::AccountId,
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	/// The campaigns ending at every block.
+	#[pallet::storage]
+	pub type Ending<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<CampaignId, T::MaxCampaignsEndingPerBlock>,
+		ValueQuery,
+	>;
+
+	/// Ended campaigns with contributions left to settle in `on_initialize`.
+	#[pallet::storage]
+	pub type Settling<T> = StorageMap<_, Blake2_128Concat, CampaignId, ()>;
+
+	#[pallet::event]
+	#[pallet::metadata(
+		T::AccountId = "AccountId",
+		T::BlockNumber = "BlockNumber",
+		BalanceOf<T> = "Balance"
+	)]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A campaign was created. [campaign, creator, goal, deadline]
+		CampaignCreated(CampaignId, T::AccountId, BalanceOf<T>, T::BlockNumber),
+		/// Funds were contributed to a campaign. [campaign, contributor, amount]
+		Contributed(CampaignId, T::AccountId, BalanceOf<T>),
+		/// A contribution to a successful campaign was paid to the artist.
+		/// [campaign, contributor, amount]
+		Paid(CampaignId, T::AccountId, BalanceOf<T>),
+		/// A contribution to a failed campaign was refunded. [campaign, contributor, amount]
+		Refunded(CampaignId, T::AccountId, BalanceOf<T>),
+		/// A reward was minted to a contributor. [campaign, contributor]
+		RewardMinted(CampaignId, T::AccountId),
+		/// A reward could not be minted to a contributor. [campaign, contributor]
+		RewardFailed(CampaignId, T::AccountId),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The campaign does not exist.
+		UnknownCampaign,
+		/// The goal of a campaign must not be zero.
+		ZeroGoal,
+		/// The campaign duration is outside of `MinDuration` and `MaxDuration`.
+		DurationOutOfRange,
+		/// More than `MaxRewardTiers` reward tiers were given.
+		TooManyTiers,
+		/// `MaxCampaignsEndingPerBlock` campaigns already end at the requested deadline.
+		TooManyEndingCampaigns,
+		/// Reward tiers must be ordered by strictly ascending minimum contribution.
+		UnorderedTiers,
+		/// The creator is not allowed to hand out one of the rewards.
+		InvalidReward,
+		/// Contributions must not be zero.
+		ZeroContribution,
+		/// The campaign has reached its deadline.
+		CampaignEnded,
+		/// The campaign has not reached its deadline yet.
+		CampaignRunning,
+		/// The account has no unsettled contribution to the campaign.
+		NoContribution,
+		/// An identifier overflowed.
+		Overflow,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Queue the campaigns ending now and settle up to `MaxSettlementsPerBlock` contributions
+		/// of ended campaigns.
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let ending = Ending::<T>::take(now);
+			let ended = ending.len() as Weight;
+			for campaign in ending {
+				Settling::<T>::insert(campaign, ());
+			}
+
+			let mut budget = T::MaxSettlementsPerBlock::get();
+			let mut visited: Weight = 0;
+			let mut settled: Weight = 0;
+			let campaigns: Vec<_> = Settling::<T>::iter_keys().take(budget as usize).collect();
+			for campaign in campaigns {
+				if budget == 0 {
+					break
+				}
+				let contributors: Vec<_> = Contributions::<T>::iter_prefix(campaign)
+					.map(|(contributor, _)| contributor)
+					.take(budget as usize)
+					.collect();
+				let count = contributors.len() as u32;
+				visited += 1;
+				settled += count as Weight;
+
+				if count < budget {
+					// Every contribution is settled; the campaign leaves the queue.
+					Settling::<T>::remove(campaign);
+				}
+				budget = budget.saturating_sub(count.max(1));
+
+				for contributor in contributors {
+					// Only fails if the contribution was settled already.
+					let _ = Self::do_settle(campaign, contributor);
+				}
+			}
+
+			T::DbWeight::get().reads_writes(2 + visited, 1 + ended + visited) +
+				settled.saturating_mul(100_000 + T::DbWeight::get().reads_writes(4, 4))
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Open a campaign raising `goal` within `duration` blocks. `tiers` must be ordered by
+		/// strictly ascending `min_contribution`.
+		#[pallet::weight({
+			// Every tier is checked and frozen by `T::Rewards`.
+			let tiers = tiers.len() as Weight;
+			10_000 + T::DbWeight::get().reads_writes(2 + tiers, 4 + tiers)
+		})]
+		pub fn create_campaign(
+			origin: OriginFor<T>,
+			goal: BalanceOf<T>,
+			duration: T::BlockNumber,
+			tiers: Vec<RewardTier<BalanceOf<T>, RewardOf<T>>>,
+		) -> DispatchResult {
+			let creator = ensure_signed(origin)?;
+			ensure!(!goal.is_zero(), Error::<T>::ZeroGoal);
+			ensure!(
+				duration >= T::MinDuration::get() && duration <= T::MaxDuration::get(),
+				Error::<T>::DurationOutOfRange
+			);
+			let tiers: BoundedVec<_, T::MaxRewardTiers> =
+				tiers.try_into().map_err(|_| Error::<T>::TooManyTiers)?;
+			ensure!(
+				tiers.windows(2).all(|pair| pair[0].min_contribution < pair[1].min_contribution),
+				Error::<T>::UnorderedTiers
+			);
+			ensure!(
+				tiers.iter().all(|tier| T::Rewards::can_mint(&creator, &tier.reward)),
+				Error::<T>::InvalidReward
+			);
+
+			let campaign = Self::next_campaign_id();
+			let next = campaign.checked_add(1).ok_or(Error::<T>::Overflow)?;
+			let deadline = frame_system::Pallet::<T>::block_number().saturating_add(duration);
+			Ending::<T>::try_mutate(deadline, |ending| {
+				ending.try_push(campaign).map_err(|_| Error::<T>::TooManyEndingCampaigns)
+			})?;
+			Campaigns::<T>::insert(
+				campaign,
+				Campaign { creator: creator.clone(), goal, raised: Zero::zero(), deadline },
+			);
+			for tier in tiers.iter() {
+				T::Rewards::freeze(&tier.reward);
+			}
+			RewardTiers::<T>::insert(campaign, tiers);
+			NextCampaignId::<T>::put(next);
+
+			Self::deposit_event(Event::CampaignCreated(campaign, creator, goal, deadline));
+			Ok(())
+		}
+
+		/// Contribute `amount` to a running campaign. The amount stays reserved in the caller's
+		/// account until the contribution is settled.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 3))]
+		pub fn contribute(
+			origin: OriginFor<T>,
+			campaign: CampaignId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let contributor = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), Error::<T>::ZeroContribution);
+
+			Campaigns::<T>::try_mutate(campaign, |maybe_campaign| -> DispatchResult {
+				let details = maybe_campaign.as_mut().ok_or(Error::<T>::UnknownCampaign)?;
+				let now = frame_system::Pallet::<T>::block_number();
+				ensure!(now < details.deadline, Error::<T>::CampaignEnded);
+
+				T::Currency::reserve(&contributor, amount)?;
+				details.raised = details.raised.saturating_add(amount);
+				Contributions::<T>::mutate(campaign, &contributor, |total| {
+					*total = total.saturating_add(amount)
+				});
+
+				Self::deposit_event(Event::Contributed(campaign, contributor.clone(), amount));
+				Ok(())
+			})
+		}
+
+		/// Settle the contribution of `contributor` to a campaign past its deadline. If the goal
+		/// was met, the contribution is paid to the artist and the reward of the best qualifying
+		/// tier is minted to the contributor; otherwise the contribution is refunded. Anyone may
+		/// settle on behalf of a contributor.
+		#[pallet::weight(100_000 + T::DbWeight::get().reads_writes(4, 4))]
+		pub fn settle(
+			origin: OriginFor<T>,
+			campaign: CampaignId,
+			contributor: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			let contributor = T::Lookup::lookup(contributor)?;
+
+			Self::do_settle(campaign, contributor)
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Settle the contribution of `contributor` to `campaign`, which must be past its
+		/// deadline.
+		fn do_settle(campaign: CampaignId, contributor: T::AccountId) -> DispatchResult {
+			let details = Self::campaign(campaign).ok_or(Error::<T>::UnknownCampaign)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(now >= details.deadline, Error::<T>::CampaignRunning);
+			let amount = Contributions::<T>::get(campaign, &contributor);
+			ensure!(!amount.is_zero(), Error::<T>::NoContribution);
+
+			if details.raised < details.goal {
+				Contributions::<T>::remove(campaign, &contributor);
+				let refunded = amount.saturating_sub(T::Currency::unreserve(&contributor, amount));
+				Self::deposit_event(Event::Refunded(campaign, contributor, refunded));
+				return Ok(())
+			}
+
+			// Only the part of the contribution still reserved can be paid, should some of it
+			// have been slashed in the meantime.
+			let unpaid = T::Currency::repatriate_reserved(
+				&contributor,
+				&details.creator,
+				amount,
+				BalanceStatus::Free,
+			)?;
+			let paid = amount.saturating_sub(unpaid);
+			Contributions::<T>::remove(campaign, &contributor);
+			Self::deposit_event(Event::Paid(campaign, contributor.clone(), paid));
+
+			let tiers = Self::reward_tiers(campaign);
+			if let Some(tier) = tiers.iter().rev().find(|tier| tier.min_contribution <= paid) {
+				// A failing mint must not keep the contribution from being settled.
+				match T::Rewards::mint(&details.creator, &tier.reward, &contributor) {
+					Ok(()) => Self::deposit_event(Event::RewardMinted(campaign, contributor)),
+					Err(_) => Self::deposit_event(Event::RewardFailed(campaign, contributor)),
+				}
+			}
+			Ok(())
+		}
+	}
+}
@@ -0,0 +1,147 @@
+use crate::{self as pallet_crowdfunding, RewardMinter};
+use frame_support::{
+	dispatch::DispatchResult,
+	parameter_types,
+	traits::{GenesisBuild, Hooks},
+};
+use frame_system as system;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+use std::cell::RefCell;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Crowdfunding: pallet_crowdfunding::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const SS58Prefix: u8 = 42;
+}
+
+impl system::Config for Test {
+	type BaseCallFilter = frame_support::traits::AllowAll;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = SS58Prefix;
+	type OnSetCode = ();
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+thread_local! {
+	pub static MINTED: RefCell<Vec<(u64, u32, u64)>> = RefCell::new(vec![]);
+	pub static FROZEN: RefCell<Vec<u32>> = RefCell::new(vec![]);
+}
+
+/// Records frozen and minted rewards; reward `0` can never be minted.
+pub struct TestRewards;
+impl RewardMinter<u64> for TestRewards {
+	type Reward = u32;
+
+	fn can_mint(_: &u64, reward: &u32) -> bool {
+		*reward != 0
+	}
+
+	fn freeze(reward: &u32) {
+		FROZEN.with(|frozen| frozen.borrow_mut().push(*reward));
+	}
+
+	fn mint(creator: &u64, reward: &u32, owner: &u64) -> DispatchResult {
+		MINTED.with(|minted| minted.borrow_mut().push((*creator, *reward, *owner)));
+		Ok(())
+	}
+}
+
+pub fn minted() -> Vec<(u64, u32, u64)> {
+	MINTED.with(|minted| minted.borrow().clone())
+}
+
+pub fn frozen() -> Vec<u32> {
+	FROZEN.with(|frozen| frozen.borrow().clone())
+}
+
+parameter_types! {
+	pub const MinDuration: u64 = 10;
+	pub const MaxDuration: u64 = 100;
+	pub const MaxRewardTiers: u32 = 2;
+	pub const MaxSettlementsPerBlock: u32 = 2;
+	pub const MaxCampaignsEndingPerBlock: u32 = 2;
+}
+
+impl pallet_crowdfunding::Config for Test {
+	type Event = Event;
+	type Currency = Balances;
+	type Rewards = TestRewards;
+	type MinDuration = MinDuration;
+	type MaxDuration = MaxDuration;
+	type MaxRewardTiers = MaxRewardTiers;
+	type MaxSettlementsPerBlock = MaxSettlementsPerBlock;
+	type MaxCampaignsEndingPerBlock = MaxCampaignsEndingPerBlock;
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(1, 100), (2, 100), (3, 100), (4, 100)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+pub fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		Crowdfunding::on_initialize(System::block_number());
+	}
+}
@@ -0,0 +1,157 @@
+use crate::{mock::*, Error, RewardTier, Settling};
+use frame_support::{assert_noop, assert_ok, traits::ReservableCurrency};
+
+fn tiers(tiers: &[(u64, u32)]) -> Vec<RewardTier<u64, u32>> {
+	tiers
+		.iter()
+		.map(|&(min_contribution, reward)| RewardTier { min_contribution, reward })
+		.collect()
+}
+
+#[test]
+fn campaigns_are_validated() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Crowdfunding::create_campaign(Origin::signed(1), 0, 10, vec![]),
+			Error::<Test>::ZeroGoal
+		);
+		assert_noop!(
+			Crowdfunding::create_campaign(Origin::signed(1), 50, 9, vec![]),
+			Error::<Test>::DurationOutOfRange
+		);
+		assert_noop!(
+			Crowdfunding::create_campaign(
+				Origin::signed(1),
+				50,
+				10,
+				tiers(&[(1, 1), (2, 2), (3, 3)])
+			),
+			Error::<Test>::TooManyTiers
+		);
+		assert_noop!(
+			Crowdfunding::create_campaign(Origin::signed(1), 50, 10, tiers(&[(5, 1), (5, 2)])),
+			Error::<Test>::UnorderedTiers
+		);
+		assert_noop!(
+			Crowdfunding::create_campaign(Origin::signed(1), 50, 10, tiers(&[(5, 0)])),
+			Error::<Test>::InvalidReward
+		);
+		assert_ok!(Crowdfunding::create_campaign(Origin::signed(1), 50, 10, tiers(&[(5, 1)])));
+		assert_eq!(Crowdfunding::campaign(0).map(|c| c.deadline), Some(11));
+	});
+}
+
+#[test]
+fn campaigns_ending_per_block_are_bounded() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Crowdfunding::create_campaign(Origin::signed(1), 50, 10, vec![]));
+		assert_ok!(Crowdfunding::create_campaign(Origin::signed(2), 50, 10, vec![]));
+		assert_noop!(
+			Crowdfunding::create_campaign(Origin::signed(3), 50, 10, vec![]),
+			Error::<Test>::TooManyEndingCampaigns
+		);
+		assert_ok!(Crowdfunding::create_campaign(Origin::signed(3), 50, 11, vec![]));
+
+		run_to_block(11);
+		assert!(Settling::<Test>::contains_key(0));
+		assert!(Settling::<Test>::contains_key(1));
+		assert!(!Settling::<Test>::contains_key(2));
+	});
+}
+
+#[test]
+fn contributions_are_reserved_until_the_deadline() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Crowdfunding::create_campaign(Origin::signed(1), 50, 10, vec![]));
+		assert_noop!(
+			Crowdfunding::contribute(Origin::signed(2), 0, 0),
+			Error::<Test>::ZeroContribution
+		);
+		assert_ok!(Crowdfunding::contribute(Origin::signed(2), 0, 20));
+		assert_ok!(Crowdfunding::contribute(Origin::signed(2), 0, 10));
+		assert_eq!(Balances::reserved_balance(2), 30);
+		assert_eq!(Crowdfunding::contribution(0, 2), 30);
+		assert_noop!(Crowdfunding::settle(Origin::signed(2), 0, 2), Error::<Test>::CampaignRunning);
+
+		System::set_block_number(11);
+		assert_noop!(
+			Crowdfunding::contribute(Origin::signed(3), 0, 10),
+			Error::<Test>::CampaignEnded
+		);
+	});
+}
+
+#[test]
+fn successful_campaigns_pay_the_artist_and_mint_rewards() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Crowdfunding::create_campaign(
+			Origin::signed(1),
+			50,
+			10,
+			tiers(&[(10, 7), (40, 8)])
+		));
+		assert_ok!(Crowdfunding::contribute(Origin::signed(2), 0, 40));
+		assert_ok!(Crowdfunding::contribute(Origin::signed(3), 0, 15));
+
+		System::set_block_number(11);
+		assert_ok!(Crowdfunding::settle(Origin::signed(1), 0, 2));
+		assert_ok!(Crowdfunding::settle(Origin::signed(1), 0, 3));
+		assert_noop!(Crowdfunding::settle(Origin::signed(1), 0, 3), Error::<Test>::NoContribution);
+
+		assert_eq!(Balances::free_balance(1), 155);
+		assert_eq!(Balances::total_balance(&2), 60);
+		assert_eq!(Balances::total_balance(&3), 85);
+		assert_eq!(minted(), vec![(1, 8, 2), (1, 7, 3)]);
+	});
+}
+
+#[test]
+fn failed_campaigns_refund_contributors() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Crowdfunding::create_campaign(Origin::signed(1), 50, 10, tiers(&[(10, 7)])));
+		assert_ok!(Crowdfunding::contribute(Origin::signed(2), 0, 40));
+
+		System::set_block_number(11);
+		assert_ok!(Crowdfunding::settle(Origin::signed(3), 0, 2));
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Balances::free_balance(2), 100);
+		assert_eq!(Balances::free_balance(1), 100);
+		assert!(minted().is_empty());
+	});
+}
+
+#[test]
+fn ended_campaigns_are_settled_in_batches() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Crowdfunding::create_campaign(Origin::signed(1), 50, 10, tiers(&[(10, 7)])));
+		assert_eq!(frozen(), vec![7]);
+		for contributor in [2, 3, 4].iter() {
+			assert_ok!(Crowdfunding::contribute(Origin::signed(*contributor), 0, 20));
+		}
+
+		run_to_block(11);
+		assert_eq!(Balances::free_balance(1), 140);
+		assert_eq!(minted().len(), 2);
+		run_to_block(12);
+		assert_eq!(Balances::free_balance(1), 160);
+		assert_eq!(minted().len(), 3);
+		assert!(!Settling::<Test>::contains_key(0));
+	});
+}
+
+#[test]
+fn only_the_reserved_part_of_a_contribution_is_paid() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Crowdfunding::create_campaign(Origin::signed(1), 50, 10, tiers(&[(20, 7)])));
+		assert_ok!(Crowdfunding::contribute(Origin::signed(2), 0, 60));
+		// Something else slashes most of the reserved contribution.
+		let _ = Balances::slash_reserved(&2, 45);
+
+		System::set_block_number(11);
+		assert_ok!(Crowdfunding::settle(Origin::signed(1), 0, 2));
+		assert_eq!(Balances::free_balance(1), 115);
+		System::assert_has_event(crate::Event::Paid(0, 2, 15).into());
+		// The paid part no longer qualifies for the reward.
+		assert!(minted().is_empty());
+	});
+}
@@ -0,0 +1,44 @@
+//! Benchmarking setup for pallet-curators
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as Curators;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::traits::{Currency, Get};
+use frame_system::RawOrigin as SystemOrigin;
+use sp_runtime::traits::Bounded;
+use sp_std::prelude::*;
+
+fn member<T: Config>(who: &T::AccountId) {
+	T::Currency::make_free_balance_be(who, BalanceOf::<T>::max_value());
+	Curators::<T>::join(SystemOrigin::Signed(who.clone()).into(), T::MinStake::get())
+		.expect("member is funded; qed");
+}
+
+benchmarks! {
+	join {
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+	}: _(SystemOrigin::Signed(caller.clone()), T::MinStake::get())
+	verify {
+		assert!(Members::<T>::contains_key(caller));
+	}
+
+	vote {
+		let caller: T::AccountId = whitelisted_caller();
+		member::<T>(&caller);
+		let proposer: T::AccountId = account("proposer", 0, 0);
+		Proposals::<T>::insert(0, Proposal {
+			proposer,
+			call: frame_system::Call::<T>::remark(vec![]).into(),
+			end: T::VotingPeriod::get(),
+			deposit: T::ProposalDepositBase::get(),
+		});
+	}: _(SystemOrigin::Signed(caller.clone()), 0, true)
+	verify {
+		assert_eq!(Votes::<T>::get(0, caller), Some(true));
+	}
+}
+
+impl_benchmark_test_suite!(Curators, crate::mock::new_test_ext(), crate::mock::Test);
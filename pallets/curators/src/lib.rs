@@ -0,0 +1,416 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// A curator DAO.
+///
+/// Accounts join the DAO by reserving a stake of at least `MinStake`. Members propose calls,
+/// such as featuring a collection or paying a grant from the DAO's pot, reserving a deposit until
+/// the proposal is closed, and vote on them for `VotingPeriod` blocks. Votes are weighted
+/// quadratically: each vote counts with the square root of the voter's stake at the time the
+/// proposal is closed, so large stakes buy influence at a diminishing rate. A proposal is
+/// accepted if its weighted ayes outnumber its weighted nays and reach `ApprovalThreshold` of the
+/// weight of all members, so that members who do not vote cannot be bypassed. Accepted proposals
+/// are dispatched with the `Curators` origin, which the runtime can require through
+/// `EnsureCurators`.
+///
+/// Quadratic weighting assumes one account per person: splitting a stake across `k` accounts
+/// raises its weight by a factor of up to `sqrt(k)`. Every account needs at least `MinStake` and
+/// the DAO has at most `MaxMembers` members, which bounds the gain, so `MinStake` should be set
+/// high enough that splitting is not worth it.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+use codec::{Decode, Encode};
+use sp_runtime::RuntimeDebug;
+
+/// Identifier of a proposal.
+pub type ProposalId = u32;
+
+/// A call proposed to the curators.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Proposal<AccountId, Call, BlockNumber, Balance> {
+	/// The member that made the proposal.
+	pub proposer: AccountId,
+	/// The call dispatched if the proposal is accepted.
+	pub call: Call,
+	/// The block from which no votes are accepted and the proposal can be closed.
+	pub end: BlockNumber,
+	/// The deposit reserved from the proposer until the proposal is closed.
+	pub deposit: Balance,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{
+		dispatch::{DispatchResult, PostDispatchInfo},
+		pallet_prelude::*,
+		traits::{Currency, ExistenceRequirement, ReservableCurrency, StorageVersion},
+		weights::GetDispatchInfo,
+		PalletId,
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::{
+		traits::{AccountIdConversion, Dispatchable, IntegerSquareRoot, Saturating, StaticLookup},
+		PerThing, Perbill,
+	};
+	use sp_std::prelude::*;
+
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub type ProposalOf<T> = Proposal<
+		<T as frame_system::Config>::AccountId,
+		<T as Config>::Proposal,
+		<T as frame_system::Config>::BlockNumber,
+		BalanceOf<T>,
+	>;
+
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(0);
+
+	/// Configure the pallet by specifying the parameters and types on which it depends.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The outer origin type.
+		type Origin: From<RawOrigin>;
+
+		/// The outer call dispatch type.
+		type Proposal: Parameter
+			+ Dispatchable<Origin = <Self as Config>::Origin, PostInfo = PostDispatchInfo>
+			+ From<frame_system::Call<Self>>
+			+ GetDispatchInfo;
+
+		/// The currency stakes are reserved in and grants are paid in.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The origin allowed to pay grants from the pot.
+		type GrantOrigin: EnsureOrigin<<Self as frame_system::Config>::Origin>;
+
+		/// The smallest stake a member must reserve.
+		#[pallet::constant]
+		type MinStake: Get<BalanceOf<Self>>;
+
+		/// The maximum number of members.
+		#[pallet::constant]
+		type MaxMembers: Get<u32>;
+
+		/// How long proposals are open for voting.
+		#[pallet::constant]
+		type VotingPeriod: Get<Self::BlockNumber>;
+
+		/// The share of the vote weight of all members that must vote aye for a proposal to be
+		/// accepted.
+		#[pallet::constant]
+		type ApprovalThreshold: Get<Perbill>;
+
+		/// The deposit reserved for every proposal.
+		#[pallet::constant]
+		type ProposalDepositBase: Get<BalanceOf<Self>>;
+
+		/// The deposit reserved for every byte of a proposed call.
+		#[pallet::constant]
+		type ProposalDepositPerByte: Get<BalanceOf<Self>>;
+
+		/// The id of the account holding the pot grants are paid from.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
+	pub struct Pallet<T>(_);
+
+	/// The origin of calls dispatched by accepted proposals.
+	#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode)]
+	pub enum RawOrigin {
+		/// The curators accepted a proposal.
+		Curators,
+	}
+
+	#[pallet::origin]
+	pub type Origin = RawOrigin;
+
+	/// The stake of every member.
+	#[pallet::storage]
+	#[pallet::getter(fn stake)]
+	pub type Members<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>>;
+
+	/// The number of members.
+	#[pallet::storage]
+	#[pallet::getter(fn member_count)]
+	pub type MemberCount<T> = StorageValue<_, u32, ValueQuery>;
+
+	/// The sum of the vote weights of all members.
+	#[pallet::storage]
+	#[pallet::getter(fn total_weight)]
+	pub type TotalWeight<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	/// The id the next proposal will get.
+	#[pallet::storage]
+	#[pallet::getter(fn next_proposal_id)]
+	pub type NextProposalId<T> = StorageValue<_, ProposalId, ValueQuery>;
+
+	/// Every open proposal.
+	#[pallet::storage]
+	#[pallet::getter(fn proposal)]
+	pub type Proposals<T: Config> = StorageMap<_, Twox64Concat, ProposalId, ProposalOf<T>>;
+
+	/// The votes on every open proposal, `true` being in favour.
+	#[pallet::storage]
+	pub type Votes<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, ProposalId, Blake2_128Concat, T::AccountId, bool>;
+
+	/// The number of open proposals every member has voted on.
+	#[pallet::storage]
+	#[pallet::getter(fn open_votes)]
+	pub type OpenVotes<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::metadata(T::AccountId = "AccountId", BalanceOf<T> = "Balance")]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An account joined the curators. [member, stake]
+		Joined(T::AccountId, BalanceOf<T>),
+		/// A member left the curators. [member]
+		Left(T::AccountId),
+		/// A call was proposed. [proposal, proposer, deposit]
+		Proposed(ProposalId, T::AccountId, BalanceOf<T>),
+		/// A member voted on a proposal. [proposal, voter, aye]
+		Voted(ProposalId, T::AccountId, bool),
+		/// A proposal was accepted and its call dispatched. [proposal, result]
+		Executed(ProposalId, DispatchResult),
+		/// A proposal was rejected. [proposal]
+		Rejected(ProposalId),
+		/// A grant was paid from the pot. [beneficiary, amount]
+		Granted(T::AccountId, BalanceOf<T>),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The account is already a member.
+		AlreadyMember,
+		/// The account is not a member.
+		NotMember,
+		/// The curators already have `MaxMembers` members.
+		TooManyMembers,
+		/// The stake is less than `MinStake`.
+		StakeTooLow,
+		/// The proposal does not exist.
+		UnknownProposal,
+		/// The voting period of the proposal is over.
+		VotingClosed,
+		/// The voting period of the proposal is not over yet.
+		VotingOpen,
+		/// The weight of the proposed call exceeds the given bound.
+		WrongWeightBound,
+		/// The member has voted on proposals that are not closed yet.
+		HasOpenVotes,
+		/// An identifier overflowed.
+		Overflow,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Join the curators, reserving `stake` from the caller.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 3))]
+		pub fn join(origin: OriginFor<T>, stake: BalanceOf<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!Members::<T>::contains_key(&who), Error::<T>::AlreadyMember);
+			ensure!(stake >= T::MinStake::get(), Error::<T>::StakeTooLow);
+			let count = Self::member_count();
+			ensure!(count < T::MaxMembers::get(), Error::<T>::TooManyMembers);
+
+			T::Currency::reserve(&who, stake)?;
+			Members::<T>::insert(&who, stake);
+			MemberCount::<T>::put(count + 1);
+			TotalWeight::<T>::mutate(|total| {
+				*total = total.saturating_add(Self::vote_weight(stake))
+			});
+
+			Self::deposit_event(Event::Joined(who, stake));
+			Ok(())
+		}
+
+		/// Leave the curators, unreserving the caller's stake. Not possible until every proposal
+		/// the caller voted on is closed, so that a proposal never holds more than `MaxMembers`
+		/// votes.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4, 4))]
+		pub fn leave(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::open_votes(&who) == 0, Error::<T>::HasOpenVotes);
+			let stake = Members::<T>::take(&who).ok_or(Error::<T>::NotMember)?;
+			OpenVotes::<T>::remove(&who);
+
+			T::Currency::unreserve(&who, stake);
+			MemberCount::<T>::mutate(|count| *count = count.saturating_sub(1));
+			TotalWeight::<T>::mutate(|total| {
+				*total = total.saturating_sub(Self::vote_weight(stake))
+			});
+
+			Self::deposit_event(Event::Left(who));
+			Ok(())
+		}
+
+		/// Propose `call` to be dispatched with the `Curators` origin, reserving
+		/// `ProposalDepositBase` plus `ProposalDepositPerByte` for each byte of the encoded call
+		/// until the proposal is closed.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 3))]
+		pub fn propose(origin: OriginFor<T>, call: Box<<T as Config>::Proposal>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Members::<T>::contains_key(&who), Error::<T>::NotMember);
+
+			let proposal = Self::next_proposal_id();
+			let next = proposal.checked_add(1).ok_or(Error::<T>::Overflow)?;
+			let len = call.encoded_size() as u32;
+			let deposit = T::ProposalDepositBase::get()
+				.saturating_add(T::ProposalDepositPerByte::get().saturating_mul(len.into()));
+			T::Currency::reserve(&who, deposit)?;
+
+			let end =
+				frame_system::Pallet::<T>::block_number().saturating_add(T::VotingPeriod::get());
+			Proposals::<T>::insert(
+				proposal,
+				Proposal { proposer: who.clone(), call: *call, end, deposit },
+			);
+			NextProposalId::<T>::put(next);
+
+			Self::deposit_event(Event::Proposed(proposal, who, deposit));
+			Ok(())
+		}
+
+		/// Vote on an open proposal, replacing any earlier vote of the caller.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 2))]
+		pub fn vote(origin: OriginFor<T>, proposal: ProposalId, aye: bool) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Members::<T>::contains_key(&who), Error::<T>::NotMember);
+			let details = Self::proposal(proposal).ok_or(Error::<T>::UnknownProposal)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() < details.end,
+				Error::<T>::VotingClosed
+			);
+
+			if Votes::<T>::mutate(proposal, &who, |vote| vote.replace(aye)).is_none() {
+				OpenVotes::<T>::mutate(&who, |count| *count = count.saturating_add(1));
+			}
+
+			Self::deposit_event(Event::Voted(proposal, who, aye));
+			Ok(())
+		}
+
+		/// Close a proposal whose voting period is over and return its deposit, dispatching its
+		/// call if the weighted ayes outnumber the weighted nays and reach `ApprovalThreshold` of
+		/// the weight of all members. `weight_bound` must be at least the weight of the proposed
+		/// call.
+		#[pallet::weight(
+			weight_bound.saturating_add(10_000).saturating_add(
+				T::DbWeight::get().reads_writes(
+					3 + 3 * T::MaxMembers::get() as Weight,
+					2 + 2 * T::MaxMembers::get() as Weight,
+				)
+			)
+		)]
+		pub fn close(
+			origin: OriginFor<T>,
+			proposal: ProposalId,
+			weight_bound: Weight,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			let details = Self::proposal(proposal).ok_or(Error::<T>::UnknownProposal)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() >= details.end,
+				Error::<T>::VotingOpen
+			);
+			ensure!(
+				details.call.get_dispatch_info().weight <= weight_bound,
+				Error::<T>::WrongWeightBound
+			);
+
+			let (mut ayes, mut nays) = (BalanceOf::<T>::default(), BalanceOf::<T>::default());
+			for (voter, aye) in Votes::<T>::drain_prefix(proposal) {
+				OpenVotes::<T>::mutate(&voter, |count| *count = count.saturating_sub(1));
+				let weight = Self::vote_weight(Self::stake(&voter).unwrap_or_default());
+				if aye {
+					ayes = ayes.saturating_add(weight);
+				} else {
+					nays = nays.saturating_add(weight);
+				}
+			}
+			Proposals::<T>::remove(proposal);
+			T::Currency::unreserve(&details.proposer, details.deposit);
+
+			let threshold = T::ApprovalThreshold::get().mul_ceil(Self::total_weight());
+			if ayes > nays && ayes >= threshold {
+				let result = details.call.dispatch(RawOrigin::Curators.into());
+				Self::deposit_event(Event::Executed(
+					proposal,
+					result.map(|_| ()).map_err(|e| e.error),
+				));
+			} else {
+				Self::deposit_event(Event::Rejected(proposal));
+			}
+			Ok(())
+		}
+
+		/// Pay a grant of `amount` from the pot to `beneficiary`.
+		#[pallet::weight(50_000 + T::DbWeight::get().reads_writes(2, 2))]
+		pub fn grant(
+			origin: OriginFor<T>,
+			beneficiary: <T::Lookup as StaticLookup>::Source,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			T::GrantOrigin::ensure_origin(origin)?;
+			let beneficiary = T::Lookup::lookup(beneficiary)?;
+
+			T::Currency::transfer(
+				&Self::account_id(),
+				&beneficiary,
+				amount,
+				ExistenceRequirement::AllowDeath,
+			)?;
+
+			Self::deposit_event(Event::Granted(beneficiary, amount));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The account holding the pot grants are paid from.
+		pub fn account_id() -> T::AccountId {
+			T::PalletId::get().into_account()
+		}
+
+		/// The weight of the votes of a member with `stake`.
+		fn vote_weight(stake: BalanceOf<T>) -> BalanceOf<T> {
+			stake.integer_sqrt()
+		}
+	}
+}
+
+/// Ensures an origin is the `Curators` origin of an accepted proposal.
+pub struct EnsureCurators;
+
+impl<O: Into<Result<RawOrigin, O>> + From<RawOrigin>> frame_support::traits::EnsureOrigin<O>
+	for EnsureCurators
+{
+	type Success = ();
+
+	fn try_origin(o: O) -> Result<Self::Success, O> {
+		o.into().map(|RawOrigin::Curators| ())
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> O {
+		O::from(RawOrigin::Curators)
+	}
+}
@@ -0,0 +1,110 @@
+use crate::{self as pallet_curators, EnsureCurators};
+use frame_support::{parameter_types, traits::GenesisBuild, PalletId};
+use frame_system as system;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+	Perbill,
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Curators: pallet_curators::{Pallet, Call, Storage, Origin, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const SS58Prefix: u8 = 42;
+}
+
+impl system::Config for Test {
+	type BaseCallFilter = frame_support::traits::AllowAll;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = SS58Prefix;
+	type OnSetCode = ();
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const MinStake: u64 = 10;
+	pub const MaxMembers: u32 = 4;
+	pub const VotingPeriod: u64 = 5;
+	pub const ApprovalThreshold: Perbill = Perbill::from_percent(50);
+	pub const ProposalDepositBase: u64 = 2;
+	pub const ProposalDepositPerByte: u64 = 1;
+	pub const CuratorsPalletId: PalletId = PalletId(*b"py/crtrs");
+}
+
+impl pallet_curators::Config for Test {
+	type Event = Event;
+	type Origin = Origin;
+	type Proposal = Call;
+	type Currency = Balances;
+	type GrantOrigin = EnsureCurators;
+	type MinStake = MinStake;
+	type MaxMembers = MaxMembers;
+	type VotingPeriod = VotingPeriod;
+	type ApprovalThreshold = ApprovalThreshold;
+	type ProposalDepositBase = ProposalDepositBase;
+	type ProposalDepositPerByte = ProposalDepositPerByte;
+	type PalletId = CuratorsPalletId;
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(1, 200), (2, 100), (3, 100), (4, 100), (5, 100)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
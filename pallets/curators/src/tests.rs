@@ -0,0 +1,136 @@
+use crate::{mock::*, Error, Event as CuratorsEvent};
+use codec::Encode;
+use frame_support::{assert_noop, assert_ok, traits::Currency};
+use sp_runtime::DispatchError;
+
+fn grant(beneficiary: u64, amount: u64) -> Box<Call> {
+	Box::new(Call::Curators(crate::Call::grant(beneficiary, amount)))
+}
+
+fn last_event() -> Event {
+	System::events().pop().expect("an event was deposited").event
+}
+
+#[test]
+fn members_reserve_a_stake() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Curators::join(Origin::signed(1), 9), Error::<Test>::StakeTooLow);
+		assert_ok!(Curators::join(Origin::signed(1), 10));
+		assert_noop!(Curators::join(Origin::signed(1), 10), Error::<Test>::AlreadyMember);
+		assert_eq!(Balances::reserved_balance(1), 10);
+
+		for member in 2..=4 {
+			assert_ok!(Curators::join(Origin::signed(member), 10));
+		}
+		assert_noop!(Curators::join(Origin::signed(5), 10), Error::<Test>::TooManyMembers);
+
+		assert_ok!(Curators::leave(Origin::signed(1)));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Curators::member_count(), 3);
+		assert_noop!(Curators::leave(Origin::signed(1)), Error::<Test>::NotMember);
+	});
+}
+
+#[test]
+fn accepted_proposals_are_dispatched_as_curators() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&Curators::account_id(), 50);
+		assert_noop!(Curators::grant(Origin::signed(1), 5, 20), DispatchError::BadOrigin);
+		assert_noop!(Curators::propose(Origin::signed(1), grant(5, 20)), Error::<Test>::NotMember);
+
+		for member in 1..=3 {
+			assert_ok!(Curators::join(Origin::signed(member), 10));
+		}
+		assert_ok!(Curators::propose(Origin::signed(1), grant(5, 20)));
+		let deposit = 2 + grant(5, 20).encoded_size() as u64;
+		assert_eq!(Balances::reserved_balance(1), 10 + deposit);
+		assert_ok!(Curators::vote(Origin::signed(1), 0, true));
+		assert_ok!(Curators::vote(Origin::signed(2), 0, true));
+		assert_noop!(Curators::close(Origin::signed(2), 0, u64::MAX), Error::<Test>::VotingOpen);
+
+		System::set_block_number(6);
+		assert_noop!(Curators::vote(Origin::signed(1), 0, false), Error::<Test>::VotingClosed);
+		assert_noop!(Curators::close(Origin::signed(2), 0, 0), Error::<Test>::WrongWeightBound);
+		assert_ok!(Curators::close(Origin::signed(2), 0, u64::MAX));
+
+		assert_eq!(last_event(), Event::Curators(CuratorsEvent::Executed(0, Ok(()))));
+		assert_eq!(Balances::free_balance(5), 120);
+		assert_eq!(Curators::proposal(0), None);
+		assert_eq!(Balances::reserved_balance(1), 10);
+	});
+}
+
+#[test]
+fn proposals_need_the_approval_of_the_threshold_of_all_members() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&Curators::account_id(), 50);
+		for member in 1..=3 {
+			assert_ok!(Curators::join(Origin::signed(member), 10));
+		}
+		assert_eq!(Curators::total_weight(), 9);
+
+		// A single aye outnumbers the nays, but falls short of half of all members.
+		assert_ok!(Curators::propose(Origin::signed(1), grant(1, 50)));
+		assert_ok!(Curators::vote(Origin::signed(1), 0, true));
+
+		System::set_block_number(6);
+		assert_ok!(Curators::close(Origin::signed(1), 0, u64::MAX));
+		assert_eq!(last_event(), Event::Curators(CuratorsEvent::Rejected(0)));
+		assert_eq!(Balances::free_balance(Curators::account_id()), 50);
+		assert_eq!(Balances::reserved_balance(1), 10);
+	});
+}
+
+#[test]
+fn votes_are_weighted_quadratically() {
+	new_test_ext().execute_with(|| {
+		// A stake of 81 outweighs two stakes of 25 linearly, but not quadratically: 9 < 5 + 5.
+		assert_ok!(Curators::join(Origin::signed(1), 81));
+		assert_ok!(Curators::join(Origin::signed(2), 25));
+		assert_ok!(Curators::join(Origin::signed(3), 25));
+		assert_ok!(Curators::propose(Origin::signed(1), grant(5, 20)));
+		assert_ok!(Curators::vote(Origin::signed(1), 0, true));
+		assert_ok!(Curators::vote(Origin::signed(2), 0, false));
+		assert_ok!(Curators::vote(Origin::signed(3), 0, false));
+
+		System::set_block_number(6);
+		assert_ok!(Curators::close(Origin::signed(1), 0, u64::MAX));
+		assert_eq!(last_event(), Event::Curators(CuratorsEvent::Rejected(0)));
+
+		// Members that left no longer count.
+		assert_ok!(Curators::leave(Origin::signed(3)));
+		assert_ok!(Curators::propose(Origin::signed(1), grant(5, 20)));
+		assert_ok!(Curators::vote(Origin::signed(1), 1, true));
+		assert_ok!(Curators::vote(Origin::signed(2), 1, false));
+
+		System::set_block_number(11);
+		assert_ok!(Curators::close(Origin::signed(1), 1, u64::MAX));
+		assert!(matches!(last_event(), Event::Curators(CuratorsEvent::Executed(1, _))));
+	});
+}
+
+#[test]
+fn members_cannot_leave_while_their_votes_are_open() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Curators::join(Origin::signed(1), 10));
+		assert_ok!(Curators::propose(Origin::signed(1), grant(5, 20)));
+
+		// Churning accounts through the DAO cannot pile up votes on the proposal.
+		for member in 2..=4 {
+			assert_ok!(Curators::join(Origin::signed(member), 10));
+			assert_ok!(Curators::vote(Origin::signed(member), 0, false));
+			assert_ok!(Curators::vote(Origin::signed(member), 0, true));
+			assert_eq!(Curators::open_votes(member), 1);
+			assert_noop!(Curators::leave(Origin::signed(member)), Error::<Test>::HasOpenVotes);
+		}
+		assert_noop!(Curators::join(Origin::signed(5), 10), Error::<Test>::TooManyMembers);
+		assert_eq!(crate::Votes::<Test>::iter_prefix(0).count(), 3);
+
+		System::set_block_number(6);
+		assert_ok!(Curators::close(Origin::signed(1), 0, u64::MAX));
+		for member in 2..=4 {
+			assert_eq!(Curators::open_votes(member), 0);
+			assert_ok!(Curators::leave(Origin::signed(member)));
+		}
+	});
+}
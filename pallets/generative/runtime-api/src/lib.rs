@@ -0,0 +1,25 @@
+//! Runtime API definition for the generative art pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The `too_many_arguments` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::too_many_arguments)]
+
+use codec::Codec;
+pub use pallet_generative::{CollectionId, GenerativeArtwork, TokenId};
+
+sp_api::decl_runtime_apis! {
+	/// The API to reproduce generative artworks from on-chain data.
+	#[api_version(2)]
+	pub trait GenerativeApi<AccountId, Hash> where
+		AccountId: Codec,
+		Hash: Codec,
+	{
+		/// The generator script of `collection` together with the seed of `token`, or `None` if
+		/// either does not exist.
+		fn generator(collection: CollectionId, token: TokenId) -> Option<GenerativeArtwork<Hash>>;
+
+		/// The account allowed to display `token` of `collection`: its user while it is rented
+		/// out, otherwise its owner. `None` if the token does not exist.
+		fn display_rights(collection: CollectionId, token: TokenId) -> Option<AccountId>;
+	}
+}
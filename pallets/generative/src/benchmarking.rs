@@ -0,0 +1,35 @@
+//! Benchmarking setup for pallet-generative
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as Generative;
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::traits::{Currency, Get};
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Bounded, StaticLookup};
+
+benchmarks! {
+	append_script_chunk {
+		let l in 1 .. T::MaxChunkLength::get();
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		Generative::<T>::create_collection(RawOrigin::Signed(caller.clone()).into(), ScriptKind::Glsl)?;
+	}: _(RawOrigin::Signed(caller), 0, vec![0u8; l as usize])
+	verify {
+		assert_eq!(Collections::<T>::get(0).map(|details| details.script_len), Some(l));
+	}
+
+	mint {
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		Generative::<T>::create_collection(RawOrigin::Signed(caller.clone()).into(), ScriptKind::Glsl)?;
+		Generative::<T>::append_script_chunk(RawOrigin::Signed(caller.clone()).into(), 0, vec![0u8; 1])?;
+		let owner = T::Lookup::unlookup(caller.clone());
+	}: _(RawOrigin::Signed(caller), 0, owner)
+	verify {
+		assert!(Tokens::<T>::contains_key(0, 0));
+	}
+}
+
+impl_benchmark_test_suite!(Generative, crate::mock::new_test_ext(), crate::mock::Test);
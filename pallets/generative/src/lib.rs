@@ -0,0 +1,539 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// On-chain generative art.
+///
+/// A collection stores its generator script (JavaScript or GLSL) on chain, split into bounded
+/// chunks, and reserves a deposit from the creator for every stored byte. Each token minted from
+/// a collection is assigned a deterministic seed derived from the parent block hash and the
+/// token id, so any renderer can reproduce the artwork from the script and the seed alone. The
+/// script is frozen once the first token has been minted.
+///
+/// The owner of a token can rent it out, e.g. to a museum or a virtual gallery, by appointing a
+/// user until an expiry block. The user holds the display rights, which the runtime API reports,
+/// and cannot be replaced by the owner before the rental expires in `on_initialize`; the user
+/// may end it early.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+use codec::{Decode, Encode};
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+/// Identifier of a generative collection.
+pub type CollectionId = u32;
+
+/// Identifier of a token within its collection.
+pub type TokenId = u32;
+
+/// The language a generator script is written in.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum ScriptKind {
+	/// A JavaScript program, typically drawing onto a canvas.
+	JavaScript,
+	/// A GLSL fragment shader.
+	Glsl,
+}
+
+/// Details of a generative collection.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct CollectionDetails<AccountId, Balance> {
+	/// The account that created the collection and owns its script.
+	pub creator: AccountId,
+	/// The language of the generator script.
+	pub kind: ScriptKind,
+	/// The number of script chunks stored so far.
+	pub chunks: u32,
+	/// The total length of the script in bytes.
+	pub script_len: u32,
+	/// The deposit reserved from the creator for the stored script.
+	pub deposit: Balance,
+	/// The number of tokens minted so far, which is also the id of the next token.
+	pub minted: TokenId,
+}
+
+/// Details of a token minted from a generative collection.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct TokenDetails<AccountId, Hash> {
+	/// The current owner of the token.
+	pub owner: AccountId,
+	/// The seed fed to the generator script to render this token.
+	pub seed: Hash,
+}
+
+/// The account a token is rented to, and until when.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct TokenUser<AccountId, BlockNumber> {
+	/// The account holding the display rights of the token.
+	pub user: AccountId,
+	/// The block in which the rental ends.
+	pub expires: BlockNumber,
+}
+
+/// Everything a renderer needs to reproduce a token.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct GenerativeArtwork<Hash> {
+	/// The language of the generator script.
+	pub kind: ScriptKind,
+	/// The full generator script.
+	pub script: Vec<u8>,
+	/// The seed of the token.
+	pub seed: Hash,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{
+		dispatch::DispatchResult,
+		pallet_prelude::*,
+		traits::{Currency, ReservableCurrency, StorageVersion},
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::{Hash, Saturating, StaticLookup, Zero};
+	use sp_std::convert::TryInto;
+
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(0);
+
+	/// Configure the pallet by specifying the parameters and types on which it depends.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency in which script deposits are reserved.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The deposit reserved for every byte of stored script.
+		#[pallet::constant]
+		type DepositPerByte: Get<BalanceOf<Self>>;
+
+		/// The maximum length of a single script chunk.
+		#[pallet::constant]
+		type MaxChunkLength: Get<u32>;
+
+		/// The maximum number of chunks a script can be split into.
+		#[pallet::constant]
+		type MaxChunks: Get<u32>;
+
+		/// The origin allowed to feature collections.
+		type FeatureOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The maximum number of rentals that can expire in the same block.
+		#[pallet::constant]
+		type MaxUsersExpiringPerBlock: Get<u32>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
+	pub struct Pallet<T>(_);
+
+	/// The id the next created collection will get.
+	#[pallet::storage]
+	#[pallet::getter(fn next_collection_id)]
+	pub type NextCollectionId<T> = StorageValue<_, CollectionId, ValueQuery>;
+
+	/// Details of every generative collection.
+	#[pallet::storage]
+	#[pallet::getter(fn collection)]
+	pub type Collections<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		CollectionId,
+		CollectionDetails<T::AccountId, BalanceOf<T>>,
+	>;
+
+	/// The generator script of each collection, keyed by chunk index.
+	#[pallet::storage]
+	pub type ScriptChunks<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		CollectionId,
+		Twox64Concat,
+		u32,
+		BoundedVec<u8, T::MaxChunkLength>,
+	>;
+
+	/// Owner and seed of every minted token.
+	#[pallet::storage]
+	#[pallet::getter(fn token)]
+	pub type Tokens<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		CollectionId,
+		Blake2_128Concat,
+		TokenId,
+		TokenDetails<T::AccountId, T::Hash>,
+	>;
+
+	/// The collections currently featured by the curators.
+	#[pallet::storage]
+	pub type Featured<T: Config> = StorageMap<_, Blake2_128Concat, CollectionId, ()>;
+
+	/// The collections whose script was frozen before their first token was minted, for example
+	/// because a crowdfunding campaign promises tokens of it.
+	#[pallet::storage]
+	pub type FrozenScripts<T: Config> = StorageMap<_, Blake2_128Concat, CollectionId, ()>;
+
+	/// The user every rented token is rented to.
+	#[pallet::storage]
+	#[pallet::getter(fn token_user)]
+	pub type Users<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		CollectionId,
+		Blake2_128Concat,
+		TokenId,
+		TokenUser<T::AccountId, T::BlockNumber>,
+	>;
+
+	/// The rented tokens by the block their rental expires in.
+	#[pallet::storage]
+	pub type UserExpiries<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<(CollectionId, TokenId), T::MaxUsersExpiringPerBlock>,
+		ValueQuery,
+	>;
+
+	#[pallet::event]
+	#[pallet::metadata(
+		T::AccountId = "AccountId",
+		T::Hash = "Hash",
+		T::BlockNumber = "BlockNumber",
+		BalanceOf<T> = "Balance"
+	)]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A generative collection was created. [collection, creator]
+		CollectionCreated(CollectionId, T::AccountId),
+		/// A chunk was appended to the script of a collection. [collection, chunk, deposit]
+		ScriptChunkStored(CollectionId, u32, BalanceOf<T>),
+		/// The script of a collection was removed and its deposit returned. [collection]
+		ScriptCleared(CollectionId),
+		/// The first token of a collection was minted, or its script was frozen in advance; the
+		/// script can no longer change. [collection]
+		ScriptFrozen(CollectionId),
+		/// A token was minted with its seed. [collection, token, owner, seed]
+		Minted(CollectionId, TokenId, T::AccountId, T::Hash),
+		/// A collection was featured or unfeatured. [collection, featured]
+		FeaturedSet(CollectionId, bool),
+		/// A token was rented out. [collection, token, user, expires]
+		UserSet(CollectionId, TokenId, T::AccountId, T::BlockNumber),
+		/// The rental of a token expired or was ended by its user. [collection, token, user]
+		UserRemoved(CollectionId, TokenId, T::AccountId),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The collection does not exist.
+		UnknownCollection,
+		/// Only the creator of a collection may do this.
+		NotCreator,
+		/// Tokens have been minted from the collection, or its script was frozen, so the script
+		/// cannot change.
+		ScriptFrozen,
+		/// The collection has no script to mint from.
+		EmptyScript,
+		/// Script chunks may not be empty.
+		EmptyChunk,
+		/// The script chunk is longer than `MaxChunkLength`.
+		ChunkTooLong,
+		/// The script already has `MaxChunks` chunks.
+		TooManyChunks,
+		/// An identifier overflowed.
+		Overflow,
+		/// The token does not exist.
+		UnknownToken,
+		/// Only the owner of a token may do this.
+		NotOwner,
+		/// The token is rented out until its rental expires.
+		UserActive,
+		/// The caller is not the user of the token.
+		NotUser,
+		/// The expiry block is not in the future.
+		ExpiryInPast,
+		/// `MaxUsersExpiringPerBlock` rentals already expire in the requested block.
+		TooManyExpiries,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// End the rentals expiring now.
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let expiring = UserExpiries::<T>::take(now);
+			let count = expiring.len() as Weight;
+			for (collection, token) in expiring {
+				if let Some(TokenUser { user, .. }) = Users::<T>::take(collection, token) {
+					Self::deposit_event(Event::UserRemoved(collection, token, user));
+				}
+			}
+
+			T::DbWeight::get().reads_writes(1 + count, 1 + count)
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Create an empty generative collection owned by the caller.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 2))]
+		pub fn create_collection(origin: OriginFor<T>, kind: ScriptKind) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let collection = Self::next_collection_id();
+			let next = collection.checked_add(1).ok_or(Error::<T>::Overflow)?;
+			Collections::<T>::insert(
+				collection,
+				CollectionDetails {
+					creator: who.clone(),
+					kind,
+					chunks: 0,
+					script_len: 0,
+					deposit: Zero::zero(),
+					minted: 0,
+				},
+			);
+			NextCollectionId::<T>::put(next);
+
+			Self::deposit_event(Event::CollectionCreated(collection, who));
+			Ok(())
+		}
+
+		/// Append a chunk to the script of a collection, reserving `DepositPerByte` for each
+		/// byte. Only possible for the creator and only until the script is frozen.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 2))]
+		pub fn append_script_chunk(
+			origin: OriginFor<T>,
+			collection: CollectionId,
+			chunk: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Collections::<T>::try_mutate(collection, |maybe_details| -> DispatchResult {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::UnknownCollection)?;
+				ensure!(details.creator == who, Error::<T>::NotCreator);
+				ensure!(!Self::is_script_frozen(collection, details), Error::<T>::ScriptFrozen);
+				ensure!(details.chunks < T::MaxChunks::get(), Error::<T>::TooManyChunks);
+				ensure!(!chunk.is_empty(), Error::<T>::EmptyChunk);
+
+				let len = chunk.len() as u32;
+				let chunk: BoundedVec<u8, T::MaxChunkLength> =
+					chunk.try_into().map_err(|_| Error::<T>::ChunkTooLong)?;
+				let deposit = T::DepositPerByte::get().saturating_mul(len.into());
+				T::Currency::reserve(&who, deposit)?;
+
+				let index = details.chunks;
+				ScriptChunks::<T>::insert(collection, index, chunk);
+				details.chunks += 1;
+				details.script_len = details.script_len.saturating_add(len);
+				details.deposit = details.deposit.saturating_add(deposit);
+
+				Self::deposit_event(Event::ScriptChunkStored(collection, index, deposit));
+				Ok(())
+			})
+		}
+
+		/// Remove the whole script of a collection and return its deposit. Only possible for the
+		/// creator and only until the script is frozen.
+		#[pallet::weight(
+			10_000 + T::DbWeight::get().reads_writes(2, 1 + T::MaxChunks::get() as Weight)
+		)]
+		pub fn clear_script(origin: OriginFor<T>, collection: CollectionId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Collections::<T>::try_mutate(collection, |maybe_details| -> DispatchResult {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::UnknownCollection)?;
+				ensure!(details.creator == who, Error::<T>::NotCreator);
+				ensure!(!Self::is_script_frozen(collection, details), Error::<T>::ScriptFrozen);
+
+				for index in 0..details.chunks {
+					ScriptChunks::<T>::remove(collection, index);
+				}
+				T::Currency::unreserve(&who, details.deposit);
+				details.chunks = 0;
+				details.script_len = 0;
+				details.deposit = Zero::zero();
+
+				Self::deposit_event(Event::ScriptCleared(collection));
+				Ok(())
+			})
+		}
+
+		/// Mint the next token of a collection to `owner`. The token's seed is derived from the
+		/// parent block hash and the token id. Minting the first token freezes the script.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 2))]
+		pub fn mint(
+			origin: OriginFor<T>,
+			collection: CollectionId,
+			owner: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let owner = T::Lookup::lookup(owner)?;
+
+			Self::do_mint(collection, &who, owner)
+		}
+
+		/// Feature a collection, or stop featuring it.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn set_featured(
+			origin: OriginFor<T>,
+			collection: CollectionId,
+			featured: bool,
+		) -> DispatchResult {
+			T::FeatureOrigin::ensure_origin(origin)?;
+			ensure!(Collections::<T>::contains_key(collection), Error::<T>::UnknownCollection);
+
+			if featured {
+				Featured::<T>::insert(collection, ());
+			} else {
+				Featured::<T>::remove(collection);
+			}
+
+			Self::deposit_event(Event::FeaturedSet(collection, featured));
+			Ok(())
+		}
+
+		/// Rent a token of the caller out to `user` until the block `expires`. Not possible while
+		/// the token is rented out.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 2))]
+		pub fn set_user(
+			origin: OriginFor<T>,
+			collection: CollectionId,
+			token: TokenId,
+			user: <T::Lookup as StaticLookup>::Source,
+			expires: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let user = T::Lookup::lookup(user)?;
+			let details = Self::token(collection, token).ok_or(Error::<T>::UnknownToken)?;
+			ensure!(details.owner == who, Error::<T>::NotOwner);
+			ensure!(!Users::<T>::contains_key(collection, token), Error::<T>::UserActive);
+			ensure!(expires > frame_system::Pallet::<T>::block_number(), Error::<T>::ExpiryInPast);
+
+			UserExpiries::<T>::try_mutate(expires, |expiring| {
+				expiring.try_push((collection, token)).map_err(|_| Error::<T>::TooManyExpiries)
+			})?;
+			Users::<T>::insert(collection, token, TokenUser { user: user.clone(), expires });
+
+			Self::deposit_event(Event::UserSet(collection, token, user, expires));
+			Ok(())
+		}
+
+		/// End the rental of a token the caller is the user of before it expires.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 2))]
+		pub fn remove_user(
+			origin: OriginFor<T>,
+			collection: CollectionId,
+			token: TokenId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let TokenUser { user, expires } =
+				Self::token_user(collection, token).ok_or(Error::<T>::NotUser)?;
+			ensure!(user == who, Error::<T>::NotUser);
+
+			Users::<T>::remove(collection, token);
+			UserExpiries::<T>::mutate(expires, |expiring| {
+				expiring.retain(|rented| rented != &(collection, token))
+			});
+
+			Self::deposit_event(Event::UserRemoved(collection, token, user));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Mint the next token of `collection` to `owner` on behalf of `creator`, who must be the
+		/// creator of the collection.
+		pub fn do_mint(
+			collection: CollectionId,
+			creator: &T::AccountId,
+			owner: T::AccountId,
+		) -> DispatchResult {
+			Collections::<T>::try_mutate(collection, |maybe_details| -> DispatchResult {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::UnknownCollection)?;
+				ensure!(&details.creator == creator, Error::<T>::NotCreator);
+				ensure!(details.chunks > 0, Error::<T>::EmptyScript);
+
+				let token = details.minted;
+				details.minted = token.checked_add(1).ok_or(Error::<T>::Overflow)?;
+				let seed = Self::seed(collection, token);
+				Tokens::<T>::insert(collection, token, TokenDetails { owner: owner.clone(), seed });
+
+				if token == 0 && !FrozenScripts::<T>::contains_key(collection) {
+					Self::deposit_event(Event::ScriptFrozen(collection));
+				}
+				Self::deposit_event(Event::Minted(collection, token, owner, seed));
+				Ok(())
+			})
+		}
+
+		/// Freeze the script of `collection` before its first token is minted, so that promises of
+		/// its tokens cannot be undermined by changing it.
+		pub fn freeze_script(collection: CollectionId) {
+			if let Some(details) = Collections::<T>::get(collection) {
+				if !Self::is_script_frozen(collection, &details) {
+					FrozenScripts::<T>::insert(collection, ());
+					Self::deposit_event(Event::ScriptFrozen(collection));
+				}
+			}
+		}
+
+		/// Whether the script of `collection` can no longer change.
+		fn is_script_frozen(
+			collection: CollectionId,
+			details: &CollectionDetails<T::AccountId, BalanceOf<T>>,
+		) -> bool {
+			details.minted > 0 || FrozenScripts::<T>::contains_key(collection)
+		}
+
+		/// The account holding the display rights of a token: its user while it is rented out,
+		/// otherwise its owner.
+		pub fn display_rights(collection: CollectionId, token: TokenId) -> Option<T::AccountId> {
+			match Self::token_user(collection, token) {
+				Some(TokenUser { user, .. }) => Some(user),
+				None => Self::token(collection, token).map(|details| details.owner),
+			}
+		}
+
+		/// Whether `collection` is currently featured.
+		pub fn is_featured(collection: CollectionId) -> bool {
+			Featured::<T>::contains_key(collection)
+		}
+
+		/// Derive the seed of a token from the parent block hash and the token's identity.
+		fn seed(collection: CollectionId, token: TokenId) -> T::Hash {
+			let parent_hash = frame_system::Pallet::<T>::parent_hash();
+			T::Hashing::hash_of(&(parent_hash, collection, token))
+		}
+
+		/// Assemble the script of `collection` and the seed of `token`, or `None` if either does
+		/// not exist.
+		pub fn generator(
+			collection: CollectionId,
+			token: TokenId,
+		) -> Option<GenerativeArtwork<T::Hash>> {
+			let details = Collections::<T>::get(collection)?;
+			let seed = Tokens::<T>::get(collection, token)?.seed;
+
+			let mut script = Vec::with_capacity(details.script_len as usize);
+			for index in 0..details.chunks {
+				script.extend_from_slice(&ScriptChunks::<T>::get(collection, index)?);
+			}
+
+			Some(GenerativeArtwork { kind: details.kind, script, seed })
+		}
+	}
+}
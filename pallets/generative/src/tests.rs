@@ -0,0 +1,215 @@
+use crate::{mock::*, Error, GenerativeArtwork, ScriptKind};
+use frame_support::{assert_noop, assert_ok};
+use sp_core::H256;
+use sp_runtime::DispatchError;
+
+fn collection_with_script(creator: u64, chunks: &[&str]) -> u32 {
+	let collection = Generative::next_collection_id();
+	assert_ok!(Generative::create_collection(Origin::signed(creator), ScriptKind::JavaScript));
+	for chunk in chunks {
+		assert_ok!(Generative::append_script_chunk(
+			Origin::signed(creator),
+			collection,
+			chunk.as_bytes().to_vec()
+		));
+	}
+	collection
+}
+
+#[test]
+fn script_chunks_reserve_a_deposit_per_byte() {
+	new_test_ext().execute_with(|| {
+		let collection = collection_with_script(1, &["draw(", "seed)"]);
+		assert_eq!(Generative::collection(collection).unwrap().script_len, 10);
+		assert_eq!(Balances::reserved_balance(1), 20);
+
+		assert_ok!(Generative::clear_script(Origin::signed(1), collection));
+		assert_eq!(Generative::collection(collection).unwrap().chunks, 0);
+		assert_eq!(Balances::reserved_balance(1), 0);
+	});
+}
+
+#[test]
+fn script_chunks_are_bounded() {
+	new_test_ext().execute_with(|| {
+		let collection = collection_with_script(1, &["a", "b", "c"]);
+		assert_noop!(
+			Generative::append_script_chunk(Origin::signed(1), collection, b"d".to_vec()),
+			Error::<Test>::TooManyChunks
+		);
+
+		let collection = collection_with_script(1, &[]);
+		assert_noop!(
+			Generative::append_script_chunk(Origin::signed(1), collection, vec![0; 9]),
+			Error::<Test>::ChunkTooLong
+		);
+		assert_noop!(
+			Generative::append_script_chunk(Origin::signed(2), collection, b"x".to_vec()),
+			Error::<Test>::NotCreator
+		);
+		// Account 3 cannot cover the deposit for eight bytes.
+		let collection = collection_with_script(3, &[]);
+		assert!(Generative::append_script_chunk(Origin::signed(3), collection, vec![0; 8]).is_err());
+	});
+}
+
+#[test]
+fn minting_assigns_deterministic_seeds_and_freezes_the_script() {
+	new_test_ext().execute_with(|| {
+		let collection = collection_with_script(1, &["main(", ")"]);
+		System::set_parent_hash(H256::repeat_byte(7));
+		assert_ok!(Generative::mint(Origin::signed(1), collection, 2));
+		assert_ok!(Generative::mint(Origin::signed(1), collection, 3));
+
+		let first = Generative::token(collection, 0).unwrap();
+		let second = Generative::token(collection, 1).unwrap();
+		assert_eq!(first.owner, 2);
+		assert_eq!(second.owner, 3);
+		assert_ne!(first.seed, second.seed);
+
+		assert_noop!(
+			Generative::append_script_chunk(Origin::signed(1), collection, b"x".to_vec()),
+			Error::<Test>::ScriptFrozen
+		);
+		assert_noop!(
+			Generative::clear_script(Origin::signed(1), collection),
+			Error::<Test>::ScriptFrozen
+		);
+	});
+}
+
+#[test]
+fn scripts_can_be_frozen_before_the_first_mint() {
+	new_test_ext().execute_with(|| {
+		let collection = collection_with_script(1, &["main()"]);
+		Generative::freeze_script(collection);
+
+		assert_noop!(
+			Generative::append_script_chunk(Origin::signed(1), collection, b"x".to_vec()),
+			Error::<Test>::ScriptFrozen
+		);
+		assert_noop!(
+			Generative::clear_script(Origin::signed(1), collection),
+			Error::<Test>::ScriptFrozen
+		);
+		assert_ok!(Generative::mint(Origin::signed(1), collection, 2));
+	});
+}
+
+#[test]
+fn cannot_mint_without_a_script() {
+	new_test_ext().execute_with(|| {
+		let collection = collection_with_script(1, &[]);
+		assert_noop!(
+			Generative::mint(Origin::signed(1), collection, 2),
+			Error::<Test>::EmptyScript
+		);
+		assert_noop!(Generative::mint(Origin::signed(1), 42, 2), Error::<Test>::UnknownCollection);
+	});
+}
+
+#[test]
+fn generator_returns_script_and_seed() {
+	new_test_ext().execute_with(|| {
+		let collection = collection_with_script(1, &["main(", ")"]);
+		assert_ok!(Generative::mint(Origin::signed(1), collection, 2));
+
+		let seed = Generative::token(collection, 0).unwrap().seed;
+		assert_eq!(
+			Generative::generator(collection, 0),
+			Some(GenerativeArtwork {
+				kind: ScriptKind::JavaScript,
+				script: b"main()".to_vec(),
+				seed
+			})
+		);
+		assert_eq!(Generative::generator(collection, 1), None);
+	});
+}
+
+#[test]
+fn only_the_feature_origin_features_collections() {
+	new_test_ext().execute_with(|| {
+		let collection = collection_with_script(1, &[]);
+		assert_noop!(
+			Generative::set_featured(Origin::signed(1), collection, true),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Generative::set_featured(Origin::root(), 42, true),
+			Error::<Test>::UnknownCollection
+		);
+
+		assert_ok!(Generative::set_featured(Origin::root(), collection, true));
+		assert!(Generative::is_featured(collection));
+		assert_ok!(Generative::set_featured(Origin::root(), collection, false));
+		assert!(!Generative::is_featured(collection));
+	});
+}
+
+#[test]
+fn owners_rent_tokens_out_until_the_rental_expires() {
+	new_test_ext().execute_with(|| {
+		let collection = collection_with_script(1, &["main()"]);
+		assert_ok!(Generative::mint(Origin::signed(1), collection, 2));
+		assert_eq!(Generative::display_rights(collection, 0), Some(2));
+
+		assert_noop!(
+			Generative::set_user(Origin::signed(3), collection, 0, 3, 5),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(
+			Generative::set_user(Origin::signed(2), collection, 0, 3, 1),
+			Error::<Test>::ExpiryInPast
+		);
+		assert_ok!(Generative::set_user(Origin::signed(2), collection, 0, 3, 5));
+		assert_eq!(Generative::display_rights(collection, 0), Some(3));
+
+		// The owner cannot take the token back or hand it to someone else during the rental.
+		assert_noop!(
+			Generative::set_user(Origin::signed(2), collection, 0, 1, 10),
+			Error::<Test>::UserActive
+		);
+
+		run_to_block(4);
+		assert_eq!(Generative::display_rights(collection, 0), Some(3));
+		run_to_block(5);
+		assert_eq!(Generative::token_user(collection, 0), None);
+		assert_eq!(Generative::display_rights(collection, 0), Some(2));
+		assert_ok!(Generative::set_user(Origin::signed(2), collection, 0, 1, 10));
+	});
+}
+
+#[test]
+fn users_can_end_a_rental_early() {
+	new_test_ext().execute_with(|| {
+		let collection = collection_with_script(1, &["main()"]);
+		assert_ok!(Generative::mint(Origin::signed(1), collection, 2));
+		assert_ok!(Generative::set_user(Origin::signed(2), collection, 0, 3, 5));
+
+		assert_noop!(
+			Generative::remove_user(Origin::signed(2), collection, 0),
+			Error::<Test>::NotUser
+		);
+		assert_ok!(Generative::remove_user(Origin::signed(3), collection, 0));
+		assert_eq!(Generative::display_rights(collection, 0), Some(2));
+		assert!(crate::UserExpiries::<Test>::get(5).is_empty());
+	});
+}
+
+#[test]
+fn rentals_expiring_per_block_are_bounded() {
+	new_test_ext().execute_with(|| {
+		let collection = collection_with_script(1, &["main()"]);
+		for _ in 0..3 {
+			assert_ok!(Generative::mint(Origin::signed(1), collection, 2));
+		}
+		assert_ok!(Generative::set_user(Origin::signed(2), collection, 0, 3, 5));
+		assert_ok!(Generative::set_user(Origin::signed(2), collection, 1, 3, 5));
+		assert_noop!(
+			Generative::set_user(Origin::signed(2), collection, 2, 3, 5),
+			Error::<Test>::TooManyExpiries
+		);
+		assert_ok!(Generative::set_user(Origin::signed(2), collection, 2, 3, 6));
+	});
+}
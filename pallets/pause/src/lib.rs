@@ -0,0 +1,193 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// Maintenance mode and emergency pause.
+///
+/// The pallet is used as the runtime's `BaseCallFilter`. `PauseOrigin` can pause whole pallets or
+/// single calls by name, and can appoint an emergency key that may pause, but not unpause, so that
+/// an incident can be contained before governance gets to vote. Calls selected by `Unpausable`,
+/// which should include system, governance and this pallet's own calls, are never filtered.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+use frame_support::traits::{CallMetadata, Filter, GetCallMetadata};
+use sp_std::prelude::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{dispatch::DispatchResult, pallet_prelude::*, traits::StorageVersion};
+	use frame_system::pallet_prelude::*;
+
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(0);
+
+	/// Configure the pallet by specifying the parameters and types on which it depends.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The origin allowed to pause, unpause and appoint the emergency key.
+		type PauseOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The calls that can never be paused.
+		type Unpausable: Filter<<Self as frame_system::Config>::Call>;
+
+		/// The maximum length of a pallet or call name.
+		#[pallet::constant]
+		type MaxNameLength: Get<u32>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
+	pub struct Pallet<T>(_);
+
+	/// The account that may pause in an emergency.
+	#[pallet::storage]
+	#[pallet::getter(fn emergency_key)]
+	pub type EmergencyKey<T: Config> = StorageValue<_, T::AccountId>;
+
+	/// The pallets all calls of which are paused, by name.
+	#[pallet::storage]
+	pub type PausedPallets<T> = StorageMap<_, Blake2_128Concat, Vec<u8>, ()>;
+
+	/// The paused calls, by pallet and call name.
+	#[pallet::storage]
+	pub type PausedCalls<T> =
+		StorageDoubleMap<_, Blake2_128Concat, Vec<u8>, Blake2_128Concat, Vec<u8>, ()>;
+
+	#[pallet::event]
+	#[pallet::metadata(T::AccountId = "AccountId")]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A pallet, or a single call of it, was paused. [pallet, call]
+		Paused(Vec<u8>, Option<Vec<u8>>),
+		/// A pallet, or a single call of it, was unpaused. [pallet, call]
+		Unpaused(Vec<u8>, Option<Vec<u8>>),
+		/// The emergency key was set or removed. [key]
+		EmergencyKeySet(Option<T::AccountId>),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// A name is longer than `MaxNameLength`.
+		NameTooLong,
+		/// The pallet or call is already paused.
+		AlreadyPaused,
+		/// The pallet or call is not paused.
+		NotPaused,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Pause all calls of `pallet`, or only its call named `call`. May also be called by the
+		/// emergency key.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 1))]
+		pub fn pause(
+			origin: OriginFor<T>,
+			pallet: Vec<u8>,
+			call: Option<Vec<u8>>,
+		) -> DispatchResult {
+			Self::ensure_pause_origin(origin)?;
+			Self::ensure_names(&pallet, &call)?;
+			ensure!(!Self::is_paused(&pallet, &call), Error::<T>::AlreadyPaused);
+
+			match &call {
+				Some(call) => PausedCalls::<T>::insert(&pallet, call, ()),
+				None => PausedPallets::<T>::insert(&pallet, ()),
+			}
+
+			Self::deposit_event(Event::Paused(pallet, call));
+			Ok(())
+		}
+
+		/// Lift the pause of `pallet`, or of its call named `call`.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn unpause(
+			origin: OriginFor<T>,
+			pallet: Vec<u8>,
+			call: Option<Vec<u8>>,
+		) -> DispatchResult {
+			T::PauseOrigin::ensure_origin(origin)?;
+			ensure!(Self::is_paused(&pallet, &call), Error::<T>::NotPaused);
+
+			match &call {
+				Some(call) => PausedCalls::<T>::remove(&pallet, call),
+				None => PausedPallets::<T>::remove(&pallet),
+			}
+
+			Self::deposit_event(Event::Unpaused(pallet, call));
+			Ok(())
+		}
+
+		/// Appoint the emergency key, or remove it with `None`.
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		pub fn set_emergency_key(
+			origin: OriginFor<T>,
+			key: Option<T::AccountId>,
+		) -> DispatchResult {
+			T::PauseOrigin::ensure_origin(origin)?;
+
+			match &key {
+				Some(key) => EmergencyKey::<T>::put(key),
+				None => EmergencyKey::<T>::kill(),
+			}
+
+			Self::deposit_event(Event::EmergencyKeySet(key));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Whether `pallet`, or its call named `call`, is paused itself. A call of a paused pallet
+		/// is not reported as paused.
+		fn is_paused(pallet: &[u8], call: &Option<Vec<u8>>) -> bool {
+			match call {
+				Some(call) => PausedCalls::<T>::contains_key(pallet, call),
+				None => PausedPallets::<T>::contains_key(pallet),
+			}
+		}
+
+		/// Ensure that `origin` is `PauseOrigin` or the emergency key.
+		fn ensure_pause_origin(origin: OriginFor<T>) -> DispatchResult {
+			let origin = match T::PauseOrigin::try_origin(origin) {
+				Ok(_) => return Ok(()),
+				Err(origin) => origin,
+			};
+			let who = ensure_signed(origin)?;
+			ensure!(Self::emergency_key().as_ref() == Some(&who), DispatchError::BadOrigin);
+			Ok(())
+		}
+
+		/// Ensure that no name is longer than `MaxNameLength`.
+		fn ensure_names(pallet: &[u8], call: &Option<Vec<u8>>) -> DispatchResult {
+			let max = T::MaxNameLength::get() as usize;
+			ensure!(
+				pallet.len() <= max && call.as_ref().map_or(true, |call| call.len() <= max),
+				Error::<T>::NameTooLong
+			);
+			Ok(())
+		}
+	}
+}
+
+/// Lets a call through unless it, or its pallet, is paused.
+impl<T: Config> Filter<<T as frame_system::Config>::Call> for Pallet<T>
+where
+	<T as frame_system::Config>::Call: GetCallMetadata,
+{
+	fn filter(call: &<T as frame_system::Config>::Call) -> bool {
+		if T::Unpausable::filter(call) {
+			return true
+		}
+		let CallMetadata { pallet_name, function_name } = call.get_call_metadata();
+		!PausedPallets::<T>::contains_key(pallet_name.as_bytes()) &&
+			!PausedCalls::<T>::contains_key(pallet_name.as_bytes(), function_name.as_bytes())
+	}
+}
@@ -0,0 +1,67 @@
+use crate::{mock::*, Error};
+use frame_support::{assert_noop, assert_ok, traits::Filter};
+use sp_runtime::traits::{BadOrigin, Dispatchable};
+
+fn transfer() -> Call {
+	Call::Balances(pallet_balances::Call::transfer(2, 10))
+}
+
+fn remark() -> Call {
+	Call::System(frame_system::Call::remark(vec![]))
+}
+
+#[test]
+fn paused_pallets_and_calls_are_filtered() {
+	new_test_ext().execute_with(|| {
+		let transfer_all = Call::Balances(pallet_balances::Call::transfer_all(2, false));
+		assert_ok!(Pause::pause(Origin::root(), b"Balances".to_vec(), Some(b"transfer".to_vec())));
+		assert!(!Pause::filter(&transfer()));
+		assert!(Pause::filter(&transfer_all));
+		assert!(transfer().dispatch(Origin::signed(1)).is_err());
+		assert_eq!(Balances::free_balance(2), 0);
+
+		assert_ok!(Pause::pause(Origin::root(), b"Balances".to_vec(), None));
+		assert!(!Pause::filter(&transfer_all));
+		assert_ok!(Pause::unpause(Origin::root(), b"Balances".to_vec(), None));
+		assert_ok!(Pause::unpause(
+			Origin::root(),
+			b"Balances".to_vec(),
+			Some(b"transfer".to_vec())
+		));
+		assert_ok!(transfer().dispatch(Origin::signed(1)));
+		assert_eq!(Balances::free_balance(2), 10);
+	});
+}
+
+#[test]
+fn unpausable_calls_are_never_filtered() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Pause::pause(Origin::root(), b"System".to_vec(), None));
+		assert!(Pause::filter(&remark()));
+		assert_ok!(remark().dispatch(Origin::signed(1)));
+
+		assert_noop!(
+			Pause::pause(Origin::root(), b"System".to_vec(), None),
+			Error::<Test>::AlreadyPaused
+		);
+		assert_noop!(
+			Pause::pause(Origin::root(), b"AVeryLongPalletName".to_vec(), None),
+			Error::<Test>::NameTooLong
+		);
+	});
+}
+
+#[test]
+fn the_emergency_key_can_only_pause() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Pause::pause(Origin::signed(5), b"Balances".to_vec(), None), BadOrigin);
+		assert_noop!(Pause::set_emergency_key(Origin::signed(5), Some(5)), BadOrigin);
+
+		assert_ok!(Pause::set_emergency_key(Origin::root(), Some(5)));
+		assert_ok!(Pause::pause(Origin::signed(5), b"Balances".to_vec(), None));
+		assert!(!Pause::filter(&transfer()));
+		assert_noop!(Pause::unpause(Origin::signed(5), b"Balances".to_vec(), None), BadOrigin);
+		assert_ok!(Pause::unpause(Origin::root(), b"Balances".to_vec(), None));
+		assert!(Pause::filter(&transfer()));
+	});
+}
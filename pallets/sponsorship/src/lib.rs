@@ -0,0 +1,248 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// Sponsored transactions.
+///
+/// A user without funds signs a call off chain and hands it to a sponsor, which submits it with
+/// `sponsor_call` and so pays the transaction fees. The call is then dispatched with the user as
+/// the signed origin. Sponsors decide what they pay for: their policy lists the calls they
+/// sponsor, and every user has a weight budget with the sponsor from which the weight of each
+/// sponsored call is deducted. The budget limits the weight, not the fee: the fee the sponsor
+/// pays also depends on the length of the transaction and the fee multiplier at the time.
+///
+/// The payload signed by the user, see `Pallet::payload`, contains the genesis hash, the
+/// transaction version of the runtime, the sponsor, the user's sponsorship nonce, the last block
+/// the call may be submitted in and the call. The nonce is incremented with every sponsored call,
+/// so a signed call can neither be replayed nor submitted on another chain, by another sponsor,
+/// after a runtime upgrade changing the meaning of calls or once it has expired.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+use codec::Encode;
+use sp_std::prelude::*;
+
+/// Identifies a call by the index of its pallet and its index within the pallet.
+pub type CallIndex = (u8, u8);
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{
+		dispatch::{DispatchResult, PostDispatchInfo},
+		pallet_prelude::*,
+		traits::StorageVersion,
+		weights::GetDispatchInfo,
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::{Dispatchable, IdentifyAccount, StaticLookup, Verify, Zero};
+	use sp_std::convert::TryInto;
+
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(0);
+
+	/// Configure the pallet by specifying the parameters and types on which it depends.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The outer call dispatch type.
+		type Call: Parameter
+			+ Dispatchable<Origin = Self::Origin, PostInfo = PostDispatchInfo>
+			+ GetDispatchInfo
+			+ From<frame_system::Call<Self>>;
+
+		/// The signature users sign sponsored calls with.
+		type OffchainSignature: Verify<Signer = Self::Signer> + Parameter;
+
+		/// The signer of `OffchainSignature`.
+		type Signer: IdentifyAccount<AccountId = Self::AccountId>;
+
+		/// The maximum number of calls a sponsor policy may list.
+		#[pallet::constant]
+		type MaxPolicyCalls: Get<u32>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
+	pub struct Pallet<T>(_);
+
+	/// The calls every sponsor is willing to pay for.
+	#[pallet::storage]
+	#[pallet::getter(fn policy)]
+	pub type Policies<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, BoundedVec<CallIndex, T::MaxPolicyCalls>>;
+
+	/// The remaining weight budget of every user with every sponsor.
+	#[pallet::storage]
+	#[pallet::getter(fn budget)]
+	pub type Budgets<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AccountId,
+		Weight,
+		ValueQuery,
+	>;
+
+	/// The nonce the next sponsored call of every user must be signed with.
+	#[pallet::storage]
+	#[pallet::getter(fn nonce)]
+	pub type Nonces<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::metadata(T::AccountId = "AccountId")]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A sponsor set its policy. [sponsor]
+		PolicySet(T::AccountId),
+		/// A sponsor removed its policy. [sponsor]
+		PolicyRemoved(T::AccountId),
+		/// A sponsor set the weight budget of a user. [sponsor, user, budget]
+		BudgetSet(T::AccountId, T::AccountId, Weight),
+		/// A sponsored call was dispatched. [sponsor, user, weight, result]
+		Sponsored(T::AccountId, T::AccountId, Weight, DispatchResult),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The policy lists more than `MaxPolicyCalls` calls.
+		TooManyCalls,
+		/// The sponsor has no policy.
+		NoPolicy,
+		/// The sponsor's policy does not cover the call.
+		CallNotSponsored,
+		/// The nonce is not the user's current sponsorship nonce.
+		BadNonce,
+		/// The user's sponsorship nonce cannot be incremented any further.
+		NonceOverflow,
+		/// The last block the call could be submitted in has passed.
+		Expired,
+		/// The signature does not match the user and payload.
+		BadSignature,
+		/// The user's budget with the sponsor does not cover the weight of the call.
+		BudgetExceeded,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set the calls the caller sponsors.
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		pub fn set_policy(origin: OriginFor<T>, calls: Vec<CallIndex>) -> DispatchResult {
+			let sponsor = ensure_signed(origin)?;
+			let calls: BoundedVec<_, T::MaxPolicyCalls> =
+				calls.try_into().map_err(|_| Error::<T>::TooManyCalls)?;
+
+			Policies::<T>::insert(&sponsor, calls);
+
+			Self::deposit_event(Event::PolicySet(sponsor));
+			Ok(())
+		}
+
+		/// Stop sponsoring calls. Budgets are kept for when a new policy is set.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn remove_policy(origin: OriginFor<T>) -> DispatchResult {
+			let sponsor = ensure_signed(origin)?;
+			ensure!(Policies::<T>::contains_key(&sponsor), Error::<T>::NoPolicy);
+
+			Policies::<T>::remove(&sponsor);
+
+			Self::deposit_event(Event::PolicyRemoved(sponsor));
+			Ok(())
+		}
+
+		/// Set the weight budget of `user` with the caller.
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		pub fn set_budget(
+			origin: OriginFor<T>,
+			user: <T::Lookup as StaticLookup>::Source,
+			budget: Weight,
+		) -> DispatchResult {
+			let sponsor = ensure_signed(origin)?;
+			let user = T::Lookup::lookup(user)?;
+
+			if budget.is_zero() {
+				Budgets::<T>::remove(&sponsor, &user);
+			} else {
+				Budgets::<T>::insert(&sponsor, &user, budget);
+			}
+
+			Self::deposit_event(Event::BudgetSet(sponsor, user, budget));
+			Ok(())
+		}
+
+		/// Dispatch `call` on behalf of `user`, who signed `Pallet::payload` for the caller as
+		/// sponsor, `nonce`, `valid_until` and `call` with `signature`. The weight of the call is
+		/// deducted from the user's budget with the caller.
+		#[pallet::weight((
+			call.get_dispatch_info()
+				.weight
+				.saturating_add(50_000)
+				.saturating_add(T::DbWeight::get().reads_writes(4, 2)),
+			DispatchClass::Normal,
+		))]
+		pub fn sponsor_call(
+			origin: OriginFor<T>,
+			user: <T::Lookup as StaticLookup>::Source,
+			nonce: u32,
+			valid_until: T::BlockNumber,
+			call: Box<<T as Config>::Call>,
+			signature: T::OffchainSignature,
+		) -> DispatchResult {
+			let sponsor = ensure_signed(origin)?;
+			let user = T::Lookup::lookup(user)?;
+			let policy = Self::policy(&sponsor).ok_or(Error::<T>::NoPolicy)?;
+			ensure!(policy.contains(&Self::call_index(&call)), Error::<T>::CallNotSponsored);
+			ensure!(nonce == Self::nonce(&user), Error::<T>::BadNonce);
+			ensure!(frame_system::Pallet::<T>::block_number() <= valid_until, Error::<T>::Expired);
+			ensure!(
+				signature.verify(&Self::payload(&sponsor, nonce, valid_until, &call)[..], &user),
+				Error::<T>::BadSignature
+			);
+			let weight = call.get_dispatch_info().weight;
+			let budget = Self::budget(&sponsor, &user);
+			ensure!(weight <= budget, Error::<T>::BudgetExceeded);
+			let next_nonce = nonce.checked_add(1).ok_or(Error::<T>::NonceOverflow)?;
+
+			Nonces::<T>::insert(&user, next_nonce);
+			Budgets::<T>::insert(&sponsor, &user, budget.saturating_sub(weight));
+			let result = call.dispatch(frame_system::RawOrigin::Signed(user.clone()).into());
+
+			Self::deposit_event(Event::Sponsored(
+				sponsor,
+				user,
+				weight,
+				result.map(|_| ()).map_err(|e| e.error),
+			));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The payload a user signs to have `call` sponsored by `sponsor` with `nonce` until the
+		/// block `valid_until`.
+		pub fn payload(
+			sponsor: &T::AccountId,
+			nonce: u32,
+			valid_until: T::BlockNumber,
+			call: &<T as Config>::Call,
+		) -> Vec<u8> {
+			let genesis_hash = frame_system::Pallet::<T>::block_hash(T::BlockNumber::zero());
+			let transaction_version = T::Version::get().transaction_version;
+			(genesis_hash, transaction_version, sponsor, nonce, valid_until, call).encode()
+		}
+
+		/// The index of the pallet of `call` and of `call` within that pallet.
+		pub fn call_index(call: &<T as Config>::Call) -> CallIndex {
+			let encoded = call.encode();
+			let index = |i: usize| encoded.get(i).copied().unwrap_or_default();
+			(index(0), index(1))
+		}
+	}
+}
@@ -0,0 +1,224 @@
+use crate::{mock::*, Error, Nonces};
+use codec::Encode;
+use frame_support::{assert_noop, assert_ok, weights::GetDispatchInfo};
+use sp_runtime::{
+	testing::TestSignature,
+	traits::{BlakeTwo256, Hash},
+};
+
+fn remark() -> Call {
+	Call::System(frame_system::Call::remark_with_event(b"hello".to_vec()))
+}
+
+/// The last block the calls signed in these tests can be submitted in.
+const VALID_UNTIL: u64 = 10;
+
+fn sign(user: u64, sponsor: u64, nonce: u32, call: &Call) -> TestSignature {
+	TestSignature(user, Sponsorship::payload(&sponsor, nonce, VALID_UNTIL, call))
+}
+
+#[test]
+fn sponsors_only_pay_for_calls_within_policy_and_budget() {
+	new_test_ext().execute_with(|| {
+		let call = remark();
+		let weight = call.get_dispatch_info().weight;
+		assert_noop!(
+			Sponsorship::sponsor_call(
+				Origin::signed(1),
+				2,
+				0,
+				VALID_UNTIL,
+				Box::new(call.clone()),
+				sign(2, 1, 0, &call)
+			),
+			Error::<Test>::NoPolicy
+		);
+
+		assert_noop!(
+			Sponsorship::set_policy(Origin::signed(1), vec![(0, 0), (0, 1), (0, 2)]),
+			Error::<Test>::TooManyCalls
+		);
+		assert_ok!(Sponsorship::set_policy(Origin::signed(1), vec![(0, 0)]));
+		assert_noop!(
+			Sponsorship::sponsor_call(
+				Origin::signed(1),
+				2,
+				0,
+				VALID_UNTIL,
+				Box::new(call.clone()),
+				sign(2, 1, 0, &call)
+			),
+			Error::<Test>::CallNotSponsored
+		);
+
+		assert_ok!(Sponsorship::set_policy(
+			Origin::signed(1),
+			vec![Sponsorship::call_index(&call)]
+		));
+		assert_ok!(Sponsorship::set_budget(Origin::signed(1), 2, weight - 1));
+		assert_noop!(
+			Sponsorship::sponsor_call(
+				Origin::signed(1),
+				2,
+				0,
+				VALID_UNTIL,
+				Box::new(call.clone()),
+				sign(2, 1, 0, &call)
+			),
+			Error::<Test>::BudgetExceeded
+		);
+	});
+}
+
+#[test]
+fn sponsored_calls_are_dispatched_as_the_user() {
+	new_test_ext().execute_with(|| {
+		let call = remark();
+		let weight = call.get_dispatch_info().weight;
+		assert_ok!(Sponsorship::set_policy(
+			Origin::signed(1),
+			vec![Sponsorship::call_index(&call)]
+		));
+		assert_ok!(Sponsorship::set_budget(Origin::signed(1), 2, weight + 10));
+
+		assert_ok!(Sponsorship::sponsor_call(
+			Origin::signed(1),
+			2,
+			0,
+			VALID_UNTIL,
+			Box::new(call.clone()),
+			sign(2, 1, 0, &call)
+		));
+		System::assert_has_event(
+			frame_system::Event::Remarked(2, BlakeTwo256::hash(b"hello")).into(),
+		);
+		System::assert_last_event(crate::Event::Sponsored(1, 2, weight, Ok(())).into());
+		assert_eq!(Sponsorship::budget(1, 2), 10);
+		assert_eq!(Sponsorship::nonce(2), 1);
+		assert_eq!(Balances::free_balance(2), 0);
+	});
+}
+
+#[test]
+fn signed_calls_cannot_be_replayed_or_forged() {
+	new_test_ext().execute_with(|| {
+		let call = remark();
+		let weight = call.get_dispatch_info().weight;
+		assert_ok!(Sponsorship::set_policy(
+			Origin::signed(1),
+			vec![Sponsorship::call_index(&call)]
+		));
+		assert_ok!(Sponsorship::set_budget(Origin::signed(1), 2, weight * 2));
+		let signature = sign(2, 1, 0, &call);
+		assert_ok!(Sponsorship::sponsor_call(
+			Origin::signed(1),
+			2,
+			0,
+			VALID_UNTIL,
+			Box::new(call.clone()),
+			signature.clone()
+		));
+
+		assert_noop!(
+			Sponsorship::sponsor_call(
+				Origin::signed(1),
+				2,
+				0,
+				VALID_UNTIL,
+				Box::new(call.clone()),
+				signature
+			),
+			Error::<Test>::BadNonce
+		);
+		// Signed by another account, or for another sponsor.
+		assert_noop!(
+			Sponsorship::sponsor_call(
+				Origin::signed(1),
+				2,
+				1,
+				VALID_UNTIL,
+				Box::new(call.clone()),
+				sign(3, 1, 1, &call)
+			),
+			Error::<Test>::BadSignature
+		);
+		assert_noop!(
+			Sponsorship::sponsor_call(
+				Origin::signed(1),
+				2,
+				1,
+				VALID_UNTIL,
+				Box::new(call.clone()),
+				sign(2, 4, 1, &call)
+			),
+			Error::<Test>::BadSignature
+		);
+	});
+}
+
+#[test]
+fn signed_calls_expire_and_are_bound_to_the_transaction_version() {
+	new_test_ext().execute_with(|| {
+		let call = remark();
+		let weight = call.get_dispatch_info().weight;
+		assert_ok!(Sponsorship::set_policy(
+			Origin::signed(1),
+			vec![Sponsorship::call_index(&call)]
+		));
+		assert_ok!(Sponsorship::set_budget(Origin::signed(1), 2, weight));
+
+		// Signed for a runtime with another transaction version.
+		let genesis_hash = System::block_hash(0);
+		let payload = (genesis_hash, 1u32, 1u64, 0u32, VALID_UNTIL, call.clone()).encode();
+		assert_noop!(
+			Sponsorship::sponsor_call(
+				Origin::signed(1),
+				2,
+				0,
+				VALID_UNTIL,
+				Box::new(call.clone()),
+				TestSignature(2, payload)
+			),
+			Error::<Test>::BadSignature
+		);
+
+		System::set_block_number(VALID_UNTIL + 1);
+		assert_noop!(
+			Sponsorship::sponsor_call(
+				Origin::signed(1),
+				2,
+				0,
+				VALID_UNTIL,
+				Box::new(call.clone()),
+				sign(2, 1, 0, &call)
+			),
+			Error::<Test>::Expired
+		);
+	});
+}
+
+#[test]
+fn nonces_do_not_wrap_around() {
+	new_test_ext().execute_with(|| {
+		let call = remark();
+		let weight = call.get_dispatch_info().weight;
+		assert_ok!(Sponsorship::set_policy(
+			Origin::signed(1),
+			vec![Sponsorship::call_index(&call)]
+		));
+		assert_ok!(Sponsorship::set_budget(Origin::signed(1), 2, weight));
+		Nonces::<Test>::insert(2, u32::MAX);
+
+		assert_noop!(
+			Sponsorship::sponsor_call(
+				Origin::signed(1),
+				2,
+				u32::MAX,
+				VALID_UNTIL,
+				Box::new(call.clone()),
+				sign(2, 1, u32::MAX, &call)
+			),
+			Error::<Test>::NonceOverflow
+		);
+	});
+}
@@ -0,0 +1,84 @@
+//! Benchmarking setup for pallet-subscriptions
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as Subscriptions;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::traits::{Currency, Get, Hooks};
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Bounded, StaticLookup};
+
+fn artist_with_tier<T: Config>() -> T::AccountId {
+	let artist: T::AccountId = account("artist", 0, 0);
+	T::Currency::make_free_balance_be(&artist, T::Currency::minimum_balance());
+	Subscriptions::<T>::create_tier(
+		RawOrigin::Signed(artist.clone()).into(),
+		T::Currency::minimum_balance(),
+		T::MinPeriod::get(),
+	)
+	.expect("tier is valid; qed");
+	artist
+}
+
+benchmarks! {
+	create_tier {
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller.clone()), T::Currency::minimum_balance(), T::MinPeriod::get())
+	verify {
+		assert!(Tiers::<T>::contains_key(caller, 0));
+	}
+
+	retire_tier {
+		let artist = artist_with_tier::<T>();
+	}: _(RawOrigin::Signed(artist.clone()), 0)
+	verify {
+		assert!(!Tiers::<T>::contains_key(artist, 0));
+	}
+
+	subscribe {
+		let artist = artist_with_tier::<T>();
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+	}: _(RawOrigin::Signed(caller.clone()), T::Lookup::unlookup(artist.clone()), 0)
+	verify {
+		assert!(Pallet::<T>::subscription(caller, artist).is_some());
+	}
+
+	cancel {
+		let artist = artist_with_tier::<T>();
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		Subscriptions::<T>::subscribe(
+			RawOrigin::Signed(caller.clone()).into(),
+			T::Lookup::unlookup(artist.clone()),
+			0,
+		)?;
+	}: _(RawOrigin::Signed(caller.clone()), T::Lookup::unlookup(artist.clone()))
+	verify {
+		assert!(Pallet::<T>::subscription(caller, artist).is_none());
+	}
+
+	on_initialize {
+		let c in 0 .. T::MaxChargesPerBlock::get();
+		let artist = artist_with_tier::<T>();
+		for i in 0 .. c {
+			let subscriber: T::AccountId = account("subscriber", i, 0);
+			T::Currency::make_free_balance_be(&subscriber, BalanceOf::<T>::max_value());
+			Subscriptions::<T>::subscribe(
+				RawOrigin::Signed(subscriber).into(),
+				T::Lookup::unlookup(artist.clone()),
+				0,
+			)?;
+		}
+		let due = frame_system::Pallet::<T>::block_number() + T::MinPeriod::get();
+		QueueHead::<T>::put(due);
+	}: {
+		Subscriptions::<T>::on_initialize(due);
+	}
+	verify {
+		assert_eq!(Due::<T>::iter_prefix(due).count(), 0);
+	}
+}
+
+impl_benchmark_test_suite!(Subscriptions, crate::mock::new_test_ext(), crate::mock::Test);
@@ -0,0 +1,411 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// Artist patronage subscriptions.
+///
+/// Artists define subscription tiers with a price and a billing period. Supporters subscribe to a
+/// tier, paying the first period up front; every following period is charged from
+/// `on_initialize`. Charges are processed from a queue with at most `MaxChargesPerBlock` per block,
+/// so a busy block pushes the remainder to the next one. A subscription that cannot be paid is
+/// cancelled automatically. Every subscriber receives a non-transferable supporter badge for the
+/// artist, which is kept after the subscription ends.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+pub use weights::WeightInfo;
+
+use codec::{Decode, Encode};
+use sp_runtime::RuntimeDebug;
+
+/// Identifier of a subscription tier, unique per artist.
+pub type TierId = u32;
+
+/// A subscription tier defined by an artist.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Tier<Balance, BlockNumber> {
+	/// The amount charged every period.
+	pub price: Balance,
+	/// The number of blocks between two charges.
+	pub period: BlockNumber,
+}
+
+/// An active subscription of a supporter to an artist.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Subscription<BlockNumber> {
+	/// The tier subscribed to.
+	pub tier: TierId,
+	/// The block at which the next period is charged.
+	pub next_charge: BlockNumber,
+}
+
+/// A non-transferable badge recording a supporter's patronage of an artist.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct SupporterBadge<BlockNumber> {
+	/// The tier of the most recent subscription.
+	pub tier: TierId,
+	/// The block at which the supporter first subscribed.
+	pub since: BlockNumber,
+	/// The number of periods paid in total.
+	pub payments: u32,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{
+		dispatch::DispatchResult,
+		pallet_prelude::*,
+		traits::{Currency, ExistenceRequirement, StorageVersion},
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::{One, Saturating, StaticLookup, Zero};
+	use sp_std::prelude::*;
+
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(0);
+
+	/// Configure the pallet by specifying the parameters and types on which it depends.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency subscriptions are paid in.
+		type Currency: Currency<Self::AccountId>;
+
+		/// The maximum number of tiers an artist can offer at the same time.
+		#[pallet::constant]
+		type MaxTiers: Get<u32>;
+
+		/// The shortest billing period a tier may have.
+		#[pallet::constant]
+		type MinPeriod: Get<Self::BlockNumber>;
+
+		/// The maximum number of recurring charges processed in a single block.
+		#[pallet::constant]
+		type MaxChargesPerBlock: Get<u32>;
+
+		/// Weight information for extrinsics and hooks in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
+	pub struct Pallet<T>(_);
+
+	/// The tiers offered by each artist.
+	#[pallet::storage]
+	#[pallet::getter(fn tier)]
+	pub type Tiers<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Twox64Concat,
+		TierId,
+		Tier<BalanceOf<T>, T::BlockNumber>,
+	>;
+
+	/// The id the next tier of an artist will get.
+	#[pallet::storage]
+	pub type NextTierId<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, TierId, ValueQuery>;
+
+	/// The number of tiers an artist currently offers.
+	#[pallet::storage]
+	pub type TierCount<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	/// Active subscriptions, keyed by subscriber and artist.
+	#[pallet::storage]
+	#[pallet::getter(fn subscription)]
+	pub type Subscriptions<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AccountId,
+		Subscription<T::BlockNumber>,
+	>;
+
+	/// The queue of pending charges: `(subscriber, artist)` pairs keyed by the block they are due.
+	#[pallet::storage]
+	pub type Due<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		Blake2_128Concat,
+		(T::AccountId, T::AccountId),
+		(),
+	>;
+
+	/// The oldest block whose due charges may not have been processed yet.
+	#[pallet::storage]
+	#[pallet::getter(fn queue_head)]
+	pub type QueueHead<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+	/// Supporter badges, keyed by supporter and artist.
+	#[pallet::storage]
+	#[pallet::getter(fn badge)]
+	pub type Badges<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AccountId,
+		SupporterBadge<T::BlockNumber>,
+	>;
+
+	#[pallet::event]
+	#[pallet::metadata(
+		T::AccountId = "AccountId",
+		T::BlockNumber = "BlockNumber",
+		BalanceOf<T> = "Balance"
+	)]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An artist created a subscription tier. [artist, tier, price, period]
+		TierCreated(T::AccountId, TierId, BalanceOf<T>, T::BlockNumber),
+		/// An artist retired a subscription tier. [artist, tier]
+		TierRetired(T::AccountId, TierId),
+		/// A supporter subscribed to an artist. [subscriber, artist, tier]
+		Subscribed(T::AccountId, T::AccountId, TierId),
+		/// A subscription period was paid. [subscriber, artist, amount]
+		Charged(T::AccountId, T::AccountId, BalanceOf<T>),
+		/// A subscriber cancelled their subscription. [subscriber, artist]
+		Cancelled(T::AccountId, T::AccountId),
+		/// A subscription was cancelled because it could not be paid. [subscriber, artist]
+		Lapsed(T::AccountId, T::AccountId),
+		/// A supporter received a badge for an artist. [supporter, artist]
+		BadgeAwarded(T::AccountId, T::AccountId),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The artist does not offer this tier.
+		UnknownTier,
+		/// The artist already offers `MaxTiers` tiers.
+		TooManyTiers,
+		/// The billing period is shorter than `MinPeriod`.
+		PeriodTooShort,
+		/// Artists cannot subscribe to themselves.
+		SelfSubscription,
+		/// The caller is already subscribed to this artist.
+		AlreadySubscribed,
+		/// The caller is not subscribed to this artist.
+		NotSubscribed,
+		/// Tiers must have a price.
+		ZeroPrice,
+		/// An identifier overflowed.
+		Overflow,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Charge the subscriptions that are due, oldest first, up to `MaxChargesPerBlock`.
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let mut budget = T::MaxChargesPerBlock::get();
+			let mut block = Self::queue_head();
+			if block.is_zero() {
+				// Nothing can be due before the first block the pallet runs in, e.g. when it is
+				// added to a live chain by a runtime upgrade, so start the queue there.
+				block = now;
+			}
+			let mut visited: Weight = 0;
+			let mut charged: Weight = 0;
+
+			while block <= now && budget > 0 {
+				let due: Vec<_> = Due::<T>::iter_prefix(block)
+					.map(|(key, _)| key)
+					.take(budget as usize)
+					.collect();
+				let count = due.len() as u32;
+				visited += 1;
+				charged += count as Weight;
+
+				for (subscriber, artist) in due {
+					Due::<T>::remove(block, (subscriber.clone(), artist.clone()));
+					Self::charge(&subscriber, &artist, block);
+				}
+
+				if count < budget {
+					// Every charge due at `block` has been processed; move on to the next block.
+					block = block.saturating_add(One::one());
+					budget = budget.saturating_sub(count.max(1));
+				} else {
+					budget = 0;
+				}
+			}
+			QueueHead::<T>::put(block);
+
+			// The benchmark covers the charges due in a single block; every further block visited
+			// costs one more read.
+			T::WeightInfo::on_initialize(charged as u32)
+				.saturating_add(T::DbWeight::get().reads(visited.saturating_sub(1)))
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Offer a new subscription tier charging `price` every `period` blocks.
+		#[pallet::weight(T::WeightInfo::create_tier())]
+		pub fn create_tier(
+			origin: OriginFor<T>,
+			price: BalanceOf<T>,
+			period: T::BlockNumber,
+		) -> DispatchResult {
+			let artist = ensure_signed(origin)?;
+			ensure!(!price.is_zero(), Error::<T>::ZeroPrice);
+			ensure!(period >= T::MinPeriod::get() && !period.is_zero(), Error::<T>::PeriodTooShort);
+			ensure!(TierCount::<T>::get(&artist) < T::MaxTiers::get(), Error::<T>::TooManyTiers);
+
+			let tier = NextTierId::<T>::get(&artist);
+			let next = tier.checked_add(1).ok_or(Error::<T>::Overflow)?;
+			Tiers::<T>::insert(&artist, tier, Tier { price, period });
+			NextTierId::<T>::insert(&artist, next);
+			TierCount::<T>::mutate(&artist, |count| *count += 1);
+
+			Self::deposit_event(Event::TierCreated(artist, tier, price, period));
+			Ok(())
+		}
+
+		/// Stop offering a tier. Existing subscriptions to it lapse at their next charge.
+		#[pallet::weight(T::WeightInfo::retire_tier())]
+		pub fn retire_tier(origin: OriginFor<T>, tier: TierId) -> DispatchResult {
+			let artist = ensure_signed(origin)?;
+			ensure!(Tiers::<T>::contains_key(&artist, tier), Error::<T>::UnknownTier);
+
+			Tiers::<T>::remove(&artist, tier);
+			TierCount::<T>::mutate(&artist, |count| *count = count.saturating_sub(1));
+
+			Self::deposit_event(Event::TierRetired(artist, tier));
+			Ok(())
+		}
+
+		/// Subscribe to a tier of `artist`. The first period is paid immediately.
+		#[pallet::weight(T::WeightInfo::subscribe())]
+		pub fn subscribe(
+			origin: OriginFor<T>,
+			artist: <T::Lookup as StaticLookup>::Source,
+			tier: TierId,
+		) -> DispatchResult {
+			let subscriber = ensure_signed(origin)?;
+			let artist = T::Lookup::lookup(artist)?;
+			ensure!(subscriber != artist, Error::<T>::SelfSubscription);
+			ensure!(
+				!Subscriptions::<T>::contains_key(&subscriber, &artist),
+				Error::<T>::AlreadySubscribed
+			);
+			let details = Tiers::<T>::get(&artist, tier).ok_or(Error::<T>::UnknownTier)?;
+
+			T::Currency::transfer(
+				&subscriber,
+				&artist,
+				details.price,
+				ExistenceRequirement::KeepAlive,
+			)?;
+
+			let now = frame_system::Pallet::<T>::block_number();
+			let next_charge = now.saturating_add(details.period);
+			Subscriptions::<T>::insert(&subscriber, &artist, Subscription { tier, next_charge });
+			Due::<T>::insert(next_charge, (subscriber.clone(), artist.clone()), ());
+			Self::record_payment(&subscriber, &artist, tier, now);
+
+			Self::deposit_event(Event::Subscribed(subscriber.clone(), artist.clone(), tier));
+			Self::deposit_event(Event::Charged(subscriber, artist, details.price));
+			Ok(())
+		}
+
+		/// Cancel a subscription. The current period is not refunded.
+		#[pallet::weight(T::WeightInfo::cancel())]
+		pub fn cancel(
+			origin: OriginFor<T>,
+			artist: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			let subscriber = ensure_signed(origin)?;
+			let artist = T::Lookup::lookup(artist)?;
+			let subscription =
+				Subscriptions::<T>::take(&subscriber, &artist).ok_or(Error::<T>::NotSubscribed)?;
+			Due::<T>::remove(subscription.next_charge, (subscriber.clone(), artist.clone()));
+
+			Self::deposit_event(Event::Cancelled(subscriber, artist));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Charge the period of a subscription that was due at `due`, cancelling the subscription
+		/// if its tier is gone or the subscriber cannot pay.
+		fn charge(subscriber: &T::AccountId, artist: &T::AccountId, due: T::BlockNumber) {
+			let subscription = match Subscriptions::<T>::get(subscriber, artist) {
+				Some(subscription) => subscription,
+				None => return,
+			};
+
+			let paid = Tiers::<T>::get(artist, subscription.tier).filter(|tier| {
+				T::Currency::transfer(
+					subscriber,
+					artist,
+					tier.price,
+					ExistenceRequirement::KeepAlive,
+				)
+				.is_ok()
+			});
+
+			match paid {
+				Some(tier) => {
+					let next_charge = due.saturating_add(tier.period);
+					Subscriptions::<T>::insert(
+						subscriber,
+						artist,
+						Subscription { tier: subscription.tier, next_charge },
+					);
+					Due::<T>::insert(next_charge, (subscriber.clone(), artist.clone()), ());
+					Self::record_payment(subscriber, artist, subscription.tier, due);
+					Self::deposit_event(Event::Charged(
+						subscriber.clone(),
+						artist.clone(),
+						tier.price,
+					));
+				},
+				None => {
+					Subscriptions::<T>::remove(subscriber, artist);
+					Self::deposit_event(Event::Lapsed(subscriber.clone(), artist.clone()));
+				},
+			}
+		}
+
+		/// Count a paid period on the supporter's badge, awarding the badge on the first payment.
+		fn record_payment(
+			supporter: &T::AccountId,
+			artist: &T::AccountId,
+			tier: TierId,
+			now: T::BlockNumber,
+		) {
+			let awarded = Badges::<T>::mutate(supporter, artist, |maybe_badge| match maybe_badge {
+				Some(badge) => {
+					badge.tier = tier;
+					badge.payments = badge.payments.saturating_add(1);
+					false
+				},
+				None => {
+					*maybe_badge = Some(SupporterBadge { tier, since: now, payments: 1 });
+					true
+				},
+			});
+			if awarded {
+				Self::deposit_event(Event::BadgeAwarded(supporter.clone(), artist.clone()));
+			}
+		}
+	}
+}
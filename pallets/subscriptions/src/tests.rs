@@ -0,0 +1,129 @@
+use crate::{mock::*, Due, Error, SupporterBadge};
+use frame_support::{assert_noop, assert_ok};
+
+#[test]
+fn tiers_are_validated() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Subscriptions::create_tier(Origin::signed(1), 10, 9),
+			Error::<Test>::PeriodTooShort
+		);
+		assert_noop!(
+			Subscriptions::create_tier(Origin::signed(1), 0, 10),
+			Error::<Test>::ZeroPrice
+		);
+
+		assert_ok!(Subscriptions::create_tier(Origin::signed(1), 10, 10));
+		assert_ok!(Subscriptions::create_tier(Origin::signed(1), 20, 10));
+		assert_noop!(
+			Subscriptions::create_tier(Origin::signed(1), 30, 10),
+			Error::<Test>::TooManyTiers
+		);
+
+		assert_ok!(Subscriptions::retire_tier(Origin::signed(1), 0));
+		assert_ok!(Subscriptions::create_tier(Origin::signed(1), 30, 10));
+		assert!(Subscriptions::tier(1, 2).is_some());
+	});
+}
+
+#[test]
+fn subscribing_pays_the_first_period_and_awards_a_badge() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Subscriptions::create_tier(Origin::signed(1), 10, 10));
+		assert_noop!(
+			Subscriptions::subscribe(Origin::signed(1), 1, 0),
+			Error::<Test>::SelfSubscription
+		);
+		assert_noop!(Subscriptions::subscribe(Origin::signed(2), 1, 1), Error::<Test>::UnknownTier);
+
+		assert_ok!(Subscriptions::subscribe(Origin::signed(2), 1, 0));
+		assert_eq!(Balances::free_balance(2), 90);
+		assert_eq!(Balances::free_balance(1), 110);
+		assert_eq!(Subscriptions::subscription(2, 1).map(|s| s.next_charge), Some(11));
+		assert_eq!(
+			Subscriptions::badge(2, 1),
+			Some(SupporterBadge { tier: 0, since: 1, payments: 1 })
+		);
+		assert_noop!(
+			Subscriptions::subscribe(Origin::signed(2), 1, 0),
+			Error::<Test>::AlreadySubscribed
+		);
+	});
+}
+
+#[test]
+fn periods_are_charged_in_on_initialize() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Subscriptions::create_tier(Origin::signed(1), 10, 10));
+		assert_ok!(Subscriptions::subscribe(Origin::signed(2), 1, 0));
+
+		run_to_block(10);
+		assert_eq!(Balances::free_balance(2), 90);
+		run_to_block(11);
+		assert_eq!(Balances::free_balance(2), 80);
+		assert_eq!(Subscriptions::subscription(2, 1).map(|s| s.next_charge), Some(21));
+		assert_eq!(Subscriptions::badge(2, 1).map(|b| b.payments), Some(2));
+	});
+}
+
+#[test]
+fn unpaid_subscriptions_lapse() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Subscriptions::create_tier(Origin::signed(1), 10, 10));
+		assert_ok!(Subscriptions::subscribe(Origin::signed(4), 1, 0));
+
+		run_to_block(11);
+		assert_eq!(Balances::free_balance(4), 5);
+		assert_eq!(Subscriptions::subscription(4, 1), None);
+		// The badge outlives the subscription.
+		assert_eq!(Subscriptions::badge(4, 1).map(|b| b.payments), Some(1));
+	});
+}
+
+#[test]
+fn subscribers_can_cancel() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Subscriptions::create_tier(Origin::signed(1), 10, 10));
+		assert_noop!(Subscriptions::cancel(Origin::signed(2), 1), Error::<Test>::NotSubscribed);
+		assert_ok!(Subscriptions::subscribe(Origin::signed(2), 1, 0));
+
+		assert_ok!(Subscriptions::cancel(Origin::signed(2), 1));
+		assert!(!Due::<Test>::contains_key(11, (2u64, 1u64)));
+		run_to_block(11);
+		assert_eq!(Balances::free_balance(2), 90);
+	});
+}
+
+#[test]
+fn charges_beyond_the_block_limit_are_queued() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Subscriptions::create_tier(Origin::signed(1), 10, 10));
+		for subscriber in [2, 3, 5].iter() {
+			assert_ok!(Subscriptions::subscribe(Origin::signed(*subscriber), 1, 0));
+		}
+		assert_eq!(Balances::free_balance(1), 130);
+
+		run_to_block(11);
+		assert_eq!(Balances::free_balance(1), 150);
+		run_to_block(12);
+		assert_eq!(Balances::free_balance(1), 160);
+		// Queued charges keep their original schedule.
+		for subscriber in [2, 3, 5].iter() {
+			assert_eq!(Subscriptions::subscription(subscriber, 1).map(|s| s.next_charge), Some(21));
+		}
+	});
+}
+
+#[test]
+fn the_queue_starts_at_the_first_block_the_pallet_runs_in() {
+	new_test_ext().execute_with(|| {
+		// As if the pallet was added to a running chain by a runtime upgrade.
+		System::set_block_number(1_000_000);
+		assert_ok!(Subscriptions::create_tier(Origin::signed(1), 10, 10));
+		assert_ok!(Subscriptions::subscribe(Origin::signed(2), 1, 0));
+
+		run_to_block(1_000_010);
+		assert_eq!(Subscriptions::queue_head(), 1_000_011);
+		assert_eq!(Balances::free_balance(2), 80);
+	});
+}
@@ -0,0 +1,39 @@
+//! Storage migrations of the template pallet.
+//!
+//! A migration moves the storage of a pallet from one version to the next. `migrate_from` only
+//! runs it while the on-chain storage version is the one it migrates from and then bumps the
+//! version, so it can stay in `on_runtime_upgrade` across upgrades and leaves migrated storage
+//! untouched. `pre_upgrade` and `post_upgrade` check the storage version with `ensure_version`
+//! around it, which `try-runtime` runs against live state.
+//!
+//! The storage of this pallet is still at version 0, so there is nothing to migrate yet. The
+//! tests run these helpers against synthetic old-format state.
+
+use frame_support::{
+	ensure,
+	traits::{Get, GetStorageVersion, PalletInfoAccess, StorageVersion},
+	weights::Weight,
+};
+
+/// Run `migrate` if the on-chain storage version of `P` is `from` and bump it to `from + 1`.
+/// Returns the weight of `migrate` and of accessing the storage version.
+pub fn migrate_from<T, P>(from: u16, migrate: impl FnOnce() -> Weight) -> Weight
+where
+	T: frame_system::Config,
+	P: GetStorageVersion + PalletInfoAccess,
+{
+	if P::on_chain_storage_version() != from {
+		return T::DbWeight::get().reads(1)
+	}
+
+	let weight = migrate();
+	StorageVersion::new(from + 1).put::<P>();
+
+	weight.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+}
+
+/// Check that the on-chain storage version of `P` is `version`.
+pub fn ensure_version<P: GetStorageVersion>(version: u16) -> Result<(), &'static str> {
+	ensure!(P::on_chain_storage_version() == version, "Unexpected storage version");
+	Ok(())
+}
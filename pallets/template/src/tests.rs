@@ -1,5 +1,9 @@
-use crate::{mock::*, Error};
-use frame_support::{assert_noop, assert_ok};
+use crate::{migrations, mock::*, Error, Something};
+use frame_support::{
+	assert_noop, assert_ok,
+	storage::unhashed,
+	traits::{GetStorageVersion, StorageVersion},
+};
 
 #[test]
 fn it_works_for_default_value() {
@@ -18,3 +22,41 @@ fn correct_error_for_none_value() {
 		assert_noop!(TemplateModule::cause_error(Origin::signed(1)), Error::<Test>::NoneValue);
 	});
 }
+
+/// A migration widening `Something` from a `u16` in a synthetic storage version 0.
+fn widen_something() -> u64 {
+	migrations::migrate_from::<Test, TemplateModule>(0, || {
+		let _ = Something::<Test>::translate::<u16, _>(|old| old.map(u32::from));
+		0
+	})
+}
+
+#[test]
+fn migrations_translate_old_format_state() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<TemplateModule>();
+		unhashed::put(&Something::<Test>::hashed_key(), &7u16);
+		assert_ok!(migrations::ensure_version::<TemplateModule>(0));
+
+		widen_something();
+		assert_ok!(migrations::ensure_version::<TemplateModule>(1));
+		assert_eq!(TemplateModule::something(), Some(7));
+		assert_eq!(
+			unhashed::get_raw(&Something::<Test>::hashed_key()).map(|raw| raw.len()),
+			Some(4)
+		);
+	});
+}
+
+#[test]
+fn migrations_leave_migrated_state_untouched() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(1).put::<TemplateModule>();
+		assert_ok!(TemplateModule::do_something(Origin::signed(1), u32::MAX));
+
+		widen_something();
+		assert_eq!(TemplateModule::on_chain_storage_version(), 1);
+		assert_eq!(TemplateModule::something(), Some(u32::MAX));
+		assert!(migrations::ensure_version::<TemplateModule>(0).is_err());
+	});
+}
@@ -0,0 +1,63 @@
+//! Benchmarking setup for pallet-ticketing
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as Ticketing;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::traits::Currency;
+use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+use sp_std::prelude::*;
+
+fn exhibition<T: Config>() -> T::AccountId {
+	let organizer: T::AccountId = account("organizer", 0, 0);
+	let price = T::Currency::minimum_balance();
+	T::Currency::make_free_balance_be(&organizer, price);
+	Ticketing::<T>::create_exhibition(
+		RawOrigin::Signed(organizer.clone()).into(),
+		1,
+		vec![price],
+		Perbill::from_percent(10),
+	)
+	.expect("exhibition is valid; qed");
+	organizer
+}
+
+benchmarks! {
+	buy {
+		let caller: T::AccountId = whitelisted_caller();
+		exhibition::<T>();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+	}: _(RawOrigin::Signed(caller.clone()), 0, 0)
+	verify {
+		assert_eq!(Tickets::<T>::get(0, 0).map(|ticket| ticket.owner), Some(caller));
+	}
+
+	buy_listed {
+		let caller: T::AccountId = whitelisted_caller();
+		exhibition::<T>();
+		let seller: T::AccountId = account("seller", 0, 0);
+		T::Currency::make_free_balance_be(&seller, BalanceOf::<T>::max_value());
+		Ticketing::<T>::buy(RawOrigin::Signed(seller.clone()).into(), 0, 0)?;
+		let price = T::Currency::minimum_balance();
+		Ticketing::<T>::list(RawOrigin::Signed(seller).into(), 0, 0, Some(price))?;
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+	}: _(RawOrigin::Signed(caller.clone()), 0, 0, price)
+	verify {
+		assert_eq!(Tickets::<T>::get(0, 0).map(|ticket| ticket.owner), Some(caller));
+	}
+
+	check_in {
+		let caller: T::AccountId = whitelisted_caller();
+		let organizer = exhibition::<T>();
+		DoorStaff::<T>::insert(0, &caller, ());
+		T::Currency::make_free_balance_be(&organizer, BalanceOf::<T>::max_value());
+		Ticketing::<T>::buy(RawOrigin::Signed(organizer).into(), 0, 0)?;
+	}: _(RawOrigin::Signed(caller), 0, 0)
+	verify {
+		assert_eq!(Tickets::<T>::get(0, 0).map(|ticket| ticket.used), Some(true));
+	}
+}
+
+impl_benchmark_test_suite!(Ticketing, crate::mock::new_test_ext(), crate::mock::Test);
@@ -0,0 +1,391 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// Tickets for exhibitions.
+///
+/// An organizer opens an exhibition with a capacity and a list of price tiers. Tickets are bought
+/// from the organizer at the price of their tier and cannot be given away freely: they change
+/// hands only by being listed for resale, at no more than the face value plus the resale markup
+/// allowed by the organizer. At the door, staff appointed by the organizer check tickets in, which
+/// marks them used and notifies `OnCheckIn`, e.g. to hand out an attendance badge.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+use codec::{Decode, Encode};
+use frame_support::weights::Weight;
+use sp_runtime::{Perbill, RuntimeDebug};
+
+/// Identifier of an exhibition.
+pub type ExhibitionId = u32;
+
+/// Identifier of a ticket within its exhibition.
+pub type TicketId = u32;
+
+/// Handler for tickets being checked in.
+pub trait OnCheckIn<AccountId> {
+	/// A ticket of `exhibition`, organized by `organizer`, was checked in by `attendee`.
+	fn on_check_in(exhibition: ExhibitionId, organizer: &AccountId, attendee: &AccountId);
+
+	/// The worst-case weight of `on_check_in`, charged by `check_in`.
+	fn on_check_in_weight() -> Weight;
+}
+
+impl<AccountId> OnCheckIn<AccountId> for () {
+	fn on_check_in(_: ExhibitionId, _: &AccountId, _: &AccountId) {}
+
+	fn on_check_in_weight() -> Weight {
+		0
+	}
+}
+
+/// Details of an exhibition.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Exhibition<AccountId> {
+	/// The account selling the tickets and appointing door staff.
+	pub organizer: AccountId,
+	/// The number of tickets that can be sold.
+	pub capacity: u32,
+	/// The number of tickets sold so far.
+	pub sold: u32,
+	/// How much above its face value a ticket may be resold.
+	pub resale_markup: Perbill,
+}
+
+/// Details of a ticket.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Ticket<AccountId, Balance> {
+	/// The current holder of the ticket.
+	pub owner: AccountId,
+	/// The price the ticket was originally sold at.
+	pub face_value: Balance,
+	/// Whether the ticket was checked in.
+	pub used: bool,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{Currency, ExistenceRequirement, StorageVersion},
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::{Saturating, StaticLookup};
+	use sp_std::{convert::TryInto, prelude::*};
+
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(0);
+
+	/// Configure the pallet by specifying the parameters and types on which it depends.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency tickets are paid in.
+		type Currency: Currency<Self::AccountId>;
+
+		/// Notified of every ticket being checked in.
+		type OnCheckIn: OnCheckIn<Self::AccountId>;
+
+		/// The maximum number of price tiers of an exhibition.
+		#[pallet::constant]
+		type MaxPriceTiers: Get<u32>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
+	pub struct Pallet<T>(_);
+
+	/// The id the next created exhibition will get.
+	#[pallet::storage]
+	#[pallet::getter(fn next_exhibition_id)]
+	pub type NextExhibitionId<T> = StorageValue<_, ExhibitionId, ValueQuery>;
+
+	/// Details of every exhibition.
+	#[pallet::storage]
+	#[pallet::getter(fn exhibition)]
+	pub type Exhibitions<T: Config> =
+		StorageMap<_, Blake2_128Concat, ExhibitionId, Exhibition<T::AccountId>>;
+
+	/// The ticket prices of every exhibition, indexed by tier.
+	#[pallet::storage]
+	#[pallet::getter(fn price_tiers)]
+	pub type PriceTiers<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		ExhibitionId,
+		BoundedVec<BalanceOf<T>, T::MaxPriceTiers>,
+		ValueQuery,
+	>;
+
+	/// The accounts allowed to check in tickets of an exhibition, besides its organizer.
+	#[pallet::storage]
+	pub type DoorStaff<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, ExhibitionId, Blake2_128Concat, T::AccountId, ()>;
+
+	/// Every sold ticket.
+	#[pallet::storage]
+	#[pallet::getter(fn ticket)]
+	pub type Tickets<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		ExhibitionId,
+		Blake2_128Concat,
+		TicketId,
+		Ticket<T::AccountId, BalanceOf<T>>,
+	>;
+
+	/// The asking price of every ticket listed for resale.
+	#[pallet::storage]
+	#[pallet::getter(fn listing)]
+	pub type Listings<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		ExhibitionId,
+		Blake2_128Concat,
+		TicketId,
+		BalanceOf<T>,
+	>;
+
+	#[pallet::event]
+	#[pallet::metadata(T::AccountId = "AccountId", BalanceOf<T> = "Balance")]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An exhibition was created. [exhibition, organizer, capacity]
+		ExhibitionCreated(ExhibitionId, T::AccountId, u32),
+		/// An account was appointed or dismissed as door staff. [exhibition, staff, appointed]
+		DoorStaffSet(ExhibitionId, T::AccountId, bool),
+		/// A ticket was sold by the organizer. [exhibition, ticket, buyer, price]
+		TicketSold(ExhibitionId, TicketId, T::AccountId, BalanceOf<T>),
+		/// A ticket was listed for resale, or delisted. [exhibition, ticket, price]
+		TicketListed(ExhibitionId, TicketId, Option<BalanceOf<T>>),
+		/// A ticket was resold. [exhibition, ticket, seller, buyer, price]
+		TicketResold(ExhibitionId, TicketId, T::AccountId, T::AccountId, BalanceOf<T>),
+		/// A ticket was checked in. [exhibition, ticket, attendee]
+		CheckedIn(ExhibitionId, TicketId, T::AccountId),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The exhibition does not exist.
+		UnknownExhibition,
+		/// The capacity of an exhibition must not be zero.
+		ZeroCapacity,
+		/// An exhibition needs at least one price tier.
+		NoPriceTiers,
+		/// More than `MaxPriceTiers` price tiers were given.
+		TooManyTiers,
+		/// The price tier does not exist.
+		UnknownTier,
+		/// All tickets of the exhibition are sold.
+		SoldOut,
+		/// The ticket does not exist.
+		UnknownTicket,
+		/// The caller does not hold the ticket.
+		NotOwner,
+		/// The ticket was already checked in.
+		TicketUsed,
+		/// The price exceeds the resale cap of the ticket.
+		AboveResaleCap,
+		/// The ticket is not listed for resale.
+		NotListed,
+		/// The asking price is higher than the buyer is willing to pay.
+		PriceTooHigh,
+		/// Only the organizer of an exhibition may do this.
+		NotOrganizer,
+		/// The caller may not check in tickets of the exhibition.
+		NotDoorStaff,
+		/// An identifier overflowed.
+		Overflow,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Open an exhibition selling `capacity` tickets at the given tier `prices`. Tickets may be
+		/// resold for at most `resale_markup` above their face value.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 3))]
+		pub fn create_exhibition(
+			origin: OriginFor<T>,
+			capacity: u32,
+			prices: Vec<BalanceOf<T>>,
+			resale_markup: Perbill,
+		) -> DispatchResult {
+			let organizer = ensure_signed(origin)?;
+			ensure!(capacity > 0, Error::<T>::ZeroCapacity);
+			ensure!(!prices.is_empty(), Error::<T>::NoPriceTiers);
+			let prices: BoundedVec<_, T::MaxPriceTiers> =
+				prices.try_into().map_err(|_| Error::<T>::TooManyTiers)?;
+
+			let exhibition = Self::next_exhibition_id();
+			let next = exhibition.checked_add(1).ok_or(Error::<T>::Overflow)?;
+			Exhibitions::<T>::insert(
+				exhibition,
+				Exhibition { organizer: organizer.clone(), capacity, sold: 0, resale_markup },
+			);
+			PriceTiers::<T>::insert(exhibition, prices);
+			NextExhibitionId::<T>::put(next);
+
+			Self::deposit_event(Event::ExhibitionCreated(exhibition, organizer, capacity));
+			Ok(())
+		}
+
+		/// Appoint `staff` as door staff of an exhibition, or dismiss them. Only the organizer
+		/// may do this.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn set_door_staff(
+			origin: OriginFor<T>,
+			exhibition: ExhibitionId,
+			staff: <T::Lookup as StaticLookup>::Source,
+			appointed: bool,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let staff = T::Lookup::lookup(staff)?;
+			let details = Self::exhibition(exhibition).ok_or(Error::<T>::UnknownExhibition)?;
+			ensure!(details.organizer == who, Error::<T>::NotOrganizer);
+
+			if appointed {
+				DoorStaff::<T>::insert(exhibition, &staff, ());
+			} else {
+				DoorStaff::<T>::remove(exhibition, &staff);
+			}
+
+			Self::deposit_event(Event::DoorStaffSet(exhibition, staff, appointed));
+			Ok(())
+		}
+
+		/// Buy a ticket of the given price `tier` from the organizer.
+		#[pallet::weight(50_000 + T::DbWeight::get().reads_writes(4, 4))]
+		pub fn buy(origin: OriginFor<T>, exhibition: ExhibitionId, tier: u32) -> DispatchResult {
+			let buyer = ensure_signed(origin)?;
+			let mut details = Self::exhibition(exhibition).ok_or(Error::<T>::UnknownExhibition)?;
+			ensure!(details.sold < details.capacity, Error::<T>::SoldOut);
+			let price = *Self::price_tiers(exhibition)
+				.get(tier as usize)
+				.ok_or(Error::<T>::UnknownTier)?;
+
+			T::Currency::transfer(
+				&buyer,
+				&details.organizer,
+				price,
+				ExistenceRequirement::KeepAlive,
+			)?;
+			let ticket = details.sold;
+			details.sold += 1;
+			Tickets::<T>::insert(
+				exhibition,
+				ticket,
+				Ticket { owner: buyer.clone(), face_value: price, used: false },
+			);
+			Exhibitions::<T>::insert(exhibition, details);
+
+			Self::deposit_event(Event::TicketSold(exhibition, ticket, buyer, price));
+			Ok(())
+		}
+
+		/// List an unused ticket for resale at `price`, or delist it with `None`. The price may
+		/// not exceed the face value plus the resale markup of the exhibition.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 1))]
+		pub fn list(
+			origin: OriginFor<T>,
+			exhibition: ExhibitionId,
+			ticket: TicketId,
+			price: Option<BalanceOf<T>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let details = Self::ticket(exhibition, ticket).ok_or(Error::<T>::UnknownTicket)?;
+			ensure!(details.owner == who, Error::<T>::NotOwner);
+			ensure!(!details.used, Error::<T>::TicketUsed);
+
+			match price {
+				Some(price) => {
+					ensure!(
+						price <= Self::resale_cap(exhibition, &details)?,
+						Error::<T>::AboveResaleCap
+					);
+					Listings::<T>::insert(exhibition, ticket, price);
+				},
+				None => Listings::<T>::remove(exhibition, ticket),
+			}
+
+			Self::deposit_event(Event::TicketListed(exhibition, ticket, price));
+			Ok(())
+		}
+
+		/// Buy a ticket listed for resale, paying at most `max_price`.
+		#[pallet::weight(50_000 + T::DbWeight::get().reads_writes(3, 4))]
+		pub fn buy_listed(
+			origin: OriginFor<T>,
+			exhibition: ExhibitionId,
+			ticket: TicketId,
+			max_price: BalanceOf<T>,
+		) -> DispatchResult {
+			let buyer = ensure_signed(origin)?;
+			let price = Self::listing(exhibition, ticket).ok_or(Error::<T>::NotListed)?;
+			ensure!(price <= max_price, Error::<T>::PriceTooHigh);
+			let mut details = Self::ticket(exhibition, ticket).ok_or(Error::<T>::UnknownTicket)?;
+
+			T::Currency::transfer(&buyer, &details.owner, price, ExistenceRequirement::KeepAlive)?;
+			let seller = sp_std::mem::replace(&mut details.owner, buyer.clone());
+			Tickets::<T>::insert(exhibition, ticket, details);
+			Listings::<T>::remove(exhibition, ticket);
+
+			Self::deposit_event(Event::TicketResold(exhibition, ticket, seller, buyer, price));
+			Ok(())
+		}
+
+		/// Check in a ticket at the door, marking it used. Only the organizer and the door staff
+		/// of the exhibition may do this.
+		#[pallet::weight(
+			10_000 +
+				T::DbWeight::get().reads_writes(3, 2) +
+				T::OnCheckIn::on_check_in_weight()
+		)]
+		pub fn check_in(
+			origin: OriginFor<T>,
+			exhibition: ExhibitionId,
+			ticket: TicketId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let organizer =
+				Self::exhibition(exhibition).ok_or(Error::<T>::UnknownExhibition)?.organizer;
+			ensure!(
+				organizer == who || DoorStaff::<T>::contains_key(exhibition, &who),
+				Error::<T>::NotDoorStaff
+			);
+			let mut details = Self::ticket(exhibition, ticket).ok_or(Error::<T>::UnknownTicket)?;
+			ensure!(!details.used, Error::<T>::TicketUsed);
+
+			details.used = true;
+			Tickets::<T>::insert(exhibition, ticket, &details);
+			Listings::<T>::remove(exhibition, ticket);
+			T::OnCheckIn::on_check_in(exhibition, &organizer, &details.owner);
+
+			Self::deposit_event(Event::CheckedIn(exhibition, ticket, details.owner));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The highest price `ticket` of `exhibition` may be resold at.
+		fn resale_cap(
+			exhibition: ExhibitionId,
+			ticket: &Ticket<T::AccountId, BalanceOf<T>>,
+		) -> Result<BalanceOf<T>, DispatchError> {
+			let markup =
+				Self::exhibition(exhibition).ok_or(Error::<T>::UnknownExhibition)?.resale_markup;
+			Ok(ticket.face_value.saturating_add(markup * ticket.face_value))
+		}
+	}
+}
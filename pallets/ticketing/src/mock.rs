@@ -0,0 +1,115 @@
+use crate::{self as pallet_ticketing, ExhibitionId, OnCheckIn};
+use frame_support::{parameter_types, traits::GenesisBuild, weights::Weight};
+use frame_system as system;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+use std::cell::RefCell;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Ticketing: pallet_ticketing::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const SS58Prefix: u8 = 42;
+}
+
+impl system::Config for Test {
+	type BaseCallFilter = frame_support::traits::AllowAll;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = SS58Prefix;
+	type OnSetCode = ();
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+thread_local! {
+	pub static CHECKED_IN: RefCell<Vec<(ExhibitionId, u64, u64)>> = RefCell::new(vec![]);
+}
+
+/// Records checked in attendees.
+pub struct TestOnCheckIn;
+impl OnCheckIn<u64> for TestOnCheckIn {
+	fn on_check_in(exhibition: ExhibitionId, organizer: &u64, attendee: &u64) {
+		CHECKED_IN
+			.with(|checked_in| checked_in.borrow_mut().push((exhibition, *organizer, *attendee)));
+	}
+
+	fn on_check_in_weight() -> Weight {
+		0
+	}
+}
+
+pub fn checked_in() -> Vec<(ExhibitionId, u64, u64)> {
+	CHECKED_IN.with(|checked_in| checked_in.borrow().clone())
+}
+
+parameter_types! {
+	pub const MaxPriceTiers: u32 = 2;
+}
+
+impl pallet_ticketing::Config for Test {
+	type Event = Event;
+	type Currency = Balances;
+	type OnCheckIn = TestOnCheckIn;
+	type MaxPriceTiers = MaxPriceTiers;
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: vec![(1, 100), (2, 100), (3, 100)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
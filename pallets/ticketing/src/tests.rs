@@ -0,0 +1,90 @@
+use crate::{mock::*, Error, Ticket};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::Perbill;
+
+#[test]
+fn tickets_are_sold_up_to_capacity() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Ticketing::create_exhibition(Origin::signed(1), 0, vec![10], Perbill::zero()),
+			Error::<Test>::ZeroCapacity
+		);
+		assert_noop!(
+			Ticketing::create_exhibition(Origin::signed(1), 2, vec![], Perbill::zero()),
+			Error::<Test>::NoPriceTiers
+		);
+		assert_noop!(
+			Ticketing::create_exhibition(Origin::signed(1), 2, vec![10, 20, 30], Perbill::zero()),
+			Error::<Test>::TooManyTiers
+		);
+		assert_ok!(Ticketing::create_exhibition(
+			Origin::signed(1),
+			2,
+			vec![10, 20],
+			Perbill::zero()
+		));
+
+		assert_noop!(Ticketing::buy(Origin::signed(2), 0, 2), Error::<Test>::UnknownTier);
+		assert_ok!(Ticketing::buy(Origin::signed(2), 0, 0));
+		assert_ok!(Ticketing::buy(Origin::signed(3), 0, 1));
+		assert_noop!(Ticketing::buy(Origin::signed(3), 0, 0), Error::<Test>::SoldOut);
+
+		assert_eq!(Ticketing::ticket(0, 1), Some(Ticket { owner: 3, face_value: 20, used: false }));
+		assert_eq!(Balances::free_balance(1), 130);
+		assert_eq!(Balances::free_balance(3), 80);
+	});
+}
+
+#[test]
+fn resale_is_capped() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Ticketing::create_exhibition(
+			Origin::signed(1),
+			1,
+			vec![20],
+			Perbill::from_percent(10)
+		));
+		assert_ok!(Ticketing::buy(Origin::signed(2), 0, 0));
+
+		assert_noop!(Ticketing::list(Origin::signed(3), 0, 0, Some(20)), Error::<Test>::NotOwner);
+		assert_noop!(
+			Ticketing::list(Origin::signed(2), 0, 0, Some(23)),
+			Error::<Test>::AboveResaleCap
+		);
+		assert_ok!(Ticketing::list(Origin::signed(2), 0, 0, Some(22)));
+
+		assert_noop!(
+			Ticketing::buy_listed(Origin::signed(3), 0, 0, 21),
+			Error::<Test>::PriceTooHigh
+		);
+		assert_ok!(Ticketing::buy_listed(Origin::signed(3), 0, 0, 22));
+		assert_eq!(Ticketing::ticket(0, 0).map(|t| t.owner), Some(3));
+		assert_eq!(Ticketing::listing(0, 0), None);
+		assert_eq!(Balances::free_balance(2), 102);
+		assert_eq!(Balances::free_balance(3), 78);
+		assert_noop!(Ticketing::buy_listed(Origin::signed(2), 0, 0, 22), Error::<Test>::NotListed);
+	});
+}
+
+#[test]
+fn door_staff_check_in_tickets() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Ticketing::create_exhibition(Origin::signed(1), 1, vec![10], Perbill::zero()));
+		assert_ok!(Ticketing::buy(Origin::signed(2), 0, 0));
+		assert_ok!(Ticketing::list(Origin::signed(2), 0, 0, Some(10)));
+
+		assert_noop!(Ticketing::check_in(Origin::signed(3), 0, 0), Error::<Test>::NotDoorStaff);
+		assert_noop!(
+			Ticketing::set_door_staff(Origin::signed(3), 0, 3, true),
+			Error::<Test>::NotOrganizer
+		);
+		assert_ok!(Ticketing::set_door_staff(Origin::signed(1), 0, 3, true));
+		assert_ok!(Ticketing::check_in(Origin::signed(3), 0, 0));
+
+		assert_eq!(Ticketing::ticket(0, 0).map(|t| t.used), Some(true));
+		assert_eq!(Ticketing::listing(0, 0), None);
+		assert_eq!(checked_in(), vec![(0, 1, 2)]);
+		assert_noop!(Ticketing::check_in(Origin::signed(1), 0, 0), Error::<Test>::TicketUsed);
+		assert_noop!(Ticketing::list(Origin::signed(2), 0, 0, Some(10)), Error::<Test>::TicketUsed);
+	});
+}
@@ -0,0 +1,206 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// Tipping artists on their artworks.
+///
+/// A tip is sent to the creators of an artwork, split equally between them. A fixed
+/// `PlatformShare` of every tip goes to the platform, and tippers may choose to give a further
+/// share to the charity account set by `CharityOrigin`. Tips can carry a short message, which is
+/// only emitted in the `Tipped` event, while the running total and count of tips are stored per
+/// artwork for display.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+use codec::{Decode, Encode};
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+/// Looks up who created an artwork.
+pub trait ArtworkCreators<ArtworkId, AccountId> {
+	/// The creators of `artwork`, or `None` if the artwork does not exist.
+	fn creators(artwork: &ArtworkId) -> Option<Vec<AccountId>>;
+}
+
+/// The aggregate tips received by an artwork.
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct TipTotal<Balance> {
+	/// The sum of all tips, including the platform and charity shares.
+	pub amount: Balance,
+	/// The number of tips.
+	pub count: u32,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{
+		dispatch::DispatchResult,
+		pallet_prelude::*,
+		traits::{Currency, ExistenceRequirement, StorageVersion},
+		transactional,
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::{
+		traits::{Saturating, Zero},
+		PerThing, Perbill,
+	};
+
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(0);
+
+	/// Configure the pallet by specifying the parameters and types on which it depends.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency tips are paid in.
+		type Currency: Currency<Self::AccountId>;
+
+		/// Identifies an artwork.
+		type ArtworkId: Parameter;
+
+		/// Looks up the creators receiving the tips of an artwork.
+		type Creators: ArtworkCreators<Self::ArtworkId, Self::AccountId>;
+
+		/// The account receiving the platform share of every tip.
+		type PlatformAccount: Get<Self::AccountId>;
+
+		/// The share of every tip that goes to the platform.
+		#[pallet::constant]
+		type PlatformShare: Get<Perbill>;
+
+		/// The origin allowed to set the charity account.
+		type CharityOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The maximum length of a tip message.
+		#[pallet::constant]
+		type MaxMessageLength: Get<u32>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
+	pub struct Pallet<T>(_);
+
+	/// The account receiving the charity share of tips, if any.
+	#[pallet::storage]
+	#[pallet::getter(fn charity)]
+	pub type Charity<T: Config> = StorageValue<_, T::AccountId>;
+
+	/// The aggregate tips received by every artwork.
+	#[pallet::storage]
+	#[pallet::getter(fn tip_total)]
+	pub type TipTotals<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::ArtworkId, TipTotal<BalanceOf<T>>, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::metadata(T::AccountId = "AccountId", BalanceOf<T> = "Balance")]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An artwork was tipped. [tipper, artwork, amount, message]
+		Tipped(T::AccountId, T::ArtworkId, BalanceOf<T>, Vec<u8>),
+		/// The charity account was changed. [charity]
+		CharitySet(Option<T::AccountId>),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The artwork does not exist or has no creators.
+		UnknownArtwork,
+		/// Tips must not be zero.
+		ZeroTip,
+		/// The message is longer than `MaxMessageLength`.
+		MessageTooLong,
+		/// A charity share was given but no charity account is set.
+		NoCharity,
+		/// The platform and charity shares together exceed the whole tip.
+		SharesTooLarge,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Tip the creators of `artwork` with `amount`, giving `charity_share` of it to the
+		/// charity account.
+		#[pallet::weight(50_000 + T::DbWeight::get().reads_writes(4, 4))]
+		#[transactional]
+		pub fn tip(
+			origin: OriginFor<T>,
+			artwork: T::ArtworkId,
+			amount: BalanceOf<T>,
+			charity_share: Perbill,
+			message: Vec<u8>,
+		) -> DispatchResult {
+			let tipper = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), Error::<T>::ZeroTip);
+			ensure!(message.len() as u32 <= T::MaxMessageLength::get(), Error::<T>::MessageTooLong);
+			let creators = T::Creators::creators(&artwork)
+				.filter(|creators| !creators.is_empty())
+				.ok_or(Error::<T>::UnknownArtwork)?;
+			ensure!(
+				T::PlatformShare::get()
+					.deconstruct()
+					.saturating_add(charity_share.deconstruct()) <=
+					Perbill::one().deconstruct(),
+				Error::<T>::SharesTooLarge
+			);
+
+			let charity_cut = charity_share * amount;
+			if !charity_cut.is_zero() {
+				let charity = Self::charity().ok_or(Error::<T>::NoCharity)?;
+				Self::pay(&tipper, &charity, charity_cut)?;
+			}
+			let platform_cut = T::PlatformShare::get() * amount;
+			Self::pay(&tipper, &T::PlatformAccount::get(), platform_cut)?;
+
+			// Split the rest equally, the first creator receiving the remainder of the division.
+			let rest = amount.saturating_sub(platform_cut).saturating_sub(charity_cut);
+			let count: BalanceOf<T> = (creators.len() as u32).into();
+			let share = rest / count;
+			let remainder = rest.saturating_sub(share.saturating_mul(count));
+			for (index, creator) in creators.iter().enumerate() {
+				let part = if index == 0 { share.saturating_add(remainder) } else { share };
+				Self::pay(&tipper, creator, part)?;
+			}
+
+			TipTotals::<T>::mutate(&artwork, |total| {
+				total.amount = total.amount.saturating_add(amount);
+				total.count = total.count.saturating_add(1);
+			});
+
+			Self::deposit_event(Event::Tipped(tipper, artwork, amount, message));
+			Ok(())
+		}
+
+		/// Set or clear the account receiving the charity share of tips.
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		pub fn set_charity(origin: OriginFor<T>, charity: Option<T::AccountId>) -> DispatchResult {
+			T::CharityOrigin::ensure_origin(origin)?;
+
+			match &charity {
+				Some(charity) => Charity::<T>::put(charity),
+				None => Charity::<T>::kill(),
+			}
+
+			Self::deposit_event(Event::CharitySet(charity));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Transfer a non-zero `amount` from `from` to `to`.
+		fn pay(from: &T::AccountId, to: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+			if amount.is_zero() {
+				return Ok(())
+			}
+			T::Currency::transfer(from, to, amount, ExistenceRequirement::KeepAlive)
+		}
+	}
+}
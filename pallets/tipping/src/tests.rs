@@ -0,0 +1,60 @@
+use crate::{mock::*, Error, TipTotal};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::Perbill;
+
+#[test]
+fn tips_are_split_between_platform_and_creators() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Tipping::tip(Origin::signed(1), 2, 101, Perbill::zero(), b"bravo".to_vec()));
+
+		// 10% of 101 goes to the platform, the rest is split with the remainder to the first
+		// creator.
+		assert_eq!(Balances::free_balance(99), 20);
+		assert_eq!(Balances::free_balance(10), 56);
+		assert_eq!(Balances::free_balance(11), 55);
+		assert_eq!(Balances::free_balance(1), 899);
+		assert_eq!(Tipping::tip_total(2), TipTotal { amount: 101, count: 1 });
+	});
+}
+
+#[test]
+fn tippers_can_give_a_share_to_charity() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Tipping::tip(Origin::signed(1), 1, 100, Perbill::from_percent(20), vec![]),
+			Error::<Test>::NoCharity
+		);
+
+		assert_ok!(Tipping::set_charity(Origin::root(), Some(50)));
+		assert_noop!(
+			Tipping::tip(Origin::signed(1), 1, 100, Perbill::from_percent(91), vec![]),
+			Error::<Test>::SharesTooLarge
+		);
+		assert_ok!(Tipping::tip(Origin::signed(1), 1, 100, Perbill::from_percent(20), vec![]));
+		assert_eq!(Balances::free_balance(50), 30);
+		assert_eq!(Balances::free_balance(99), 20);
+		assert_eq!(Balances::free_balance(10), 80);
+	});
+}
+
+#[test]
+fn invalid_tips_are_rejected() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Tipping::tip(Origin::signed(1), 3, 100, Perbill::zero(), vec![]),
+			Error::<Test>::UnknownArtwork
+		);
+		assert_noop!(
+			Tipping::tip(Origin::signed(1), 1, 0, Perbill::zero(), vec![]),
+			Error::<Test>::ZeroTip
+		);
+		assert_noop!(
+			Tipping::tip(Origin::signed(1), 1, 100, Perbill::zero(), vec![0; 17]),
+			Error::<Test>::MessageTooLong
+		);
+		assert_noop!(
+			Tipping::set_charity(Origin::signed(1), Some(50)),
+			sp_runtime::DispatchError::BadOrigin
+		);
+	});
+}
@@ -6,12 +6,17 @@
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
+use frame_system::{EnsureOneOf, EnsureRoot};
 use pallet_grandpa::{
 	fg_primitives, AuthorityId as GrandpaId, AuthorityList as GrandpaAuthorityList,
 };
 use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
-use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
+use sp_core::{
+	crypto::KeyTypeId,
+	u32_trait::{_1, _2, _3},
+	OpaqueMetadata,
+};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
 	traits::{AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, Verify},
@@ -26,23 +31,57 @@ use sp_version::RuntimeVersion;
 // A few exports that help ease life for downstream crates.
 pub use frame_support::{
 	construct_runtime, parameter_types,
-	traits::{KeyOwnerProofSystem, Randomness, StorageInfo},
+	traits::{Currency, Imbalance, KeyOwnerProofSystem, OnUnbalanced, Randomness, StorageInfo},
 	weights::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
 		IdentityFee, Weight,
 	},
-	StorageValue,
+	PalletId, StorageValue,
 };
 pub use pallet_balances::Call as BalancesCall;
 pub use pallet_timestamp::Call as TimestampCall;
-use pallet_transaction_payment::CurrencyAdapter;
+pub use pallet_transaction_payment::Multiplier;
+use pallet_transaction_payment::{CurrencyAdapter, TargetedFeeAdjustment};
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
-pub use sp_runtime::{Perbill, Permill};
+pub use sp_runtime::{FixedPointNumber, Perbill, Permill, Perquintill};
 
 /// Import the template pallet.
 pub use pallet_template;
 
+/// Import the generative art pallet.
+pub use pallet_generative;
+
+/// Import the artist subscriptions pallet.
+pub use pallet_subscriptions;
+
+/// Import the crowdfunding pallet.
+pub use pallet_crowdfunding;
+
+/// Import the tipping pallet.
+pub use pallet_tipping;
+
+/// Import the badges pallet.
+pub use pallet_badges;
+
+/// Import the exhibition ticketing pallet.
+pub use pallet_ticketing;
+
+/// Import the curator DAO pallet.
+pub use pallet_curators;
+
+/// Import the verified artists pallet.
+pub use pallet_artists;
+
+/// Import the sponsored transactions pallet.
+pub use pallet_sponsorship;
+
+/// Import the asset fee payment pallet.
+pub use pallet_asset_fees;
+
+/// Import the emergency pause pallet.
+pub use pallet_pause;
+
 /// An index to a block.
 pub type BlockNumber = u32;
 
@@ -98,10 +137,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 };
 
 /// This determines the average expected block time that we are targeting.
@@ -143,8 +182,8 @@ parameter_types! {
 // Configure FRAME pallets to include in runtime.
 
 impl frame_system::Config for Runtime {
-	/// The basic call filter to use in dispatchable.
-	type BaseCallFilter = frame_support::traits::AllowAll;
+	/// The basic call filter to use in dispatchable. Filters out paused pallets and calls.
+	type BaseCallFilter = Pause;
 	/// Block & extrinsics weights: base values and limits.
 	type BlockWeights = BlockWeights;
 	/// The maximum length of a block (in bytes).
@@ -251,13 +290,40 @@ impl pallet_balances::Config for Runtime {
 
 parameter_types! {
 	pub const TransactionByteFee: Balance = 1;
+	/// The portion of a block's normal dispatch weight above which fees start to rise.
+	pub const TargetBlockFullness: Perquintill = Perquintill::from_percent(25);
+	/// How fast the multiplier responds to blocks off target.
+	pub AdjustmentVariable: Multiplier = Multiplier::saturating_from_rational(3, 100_000);
+	/// The multiplier never decays below this value.
+	pub MinimumMultiplier: Multiplier = Multiplier::saturating_from_rational(1, 1_000_000u128);
+}
+
+type NegativeImbalance = <Balances as Currency<AccountId>>::NegativeImbalance;
+
+/// Sends most of the transaction fees and tips to the treasury and burns the rest.
+pub struct DealWithFees;
+
+impl OnUnbalanced<NegativeImbalance> for DealWithFees {
+	fn on_unbalanceds<B>(mut fees_then_tips: impl Iterator<Item = NegativeImbalance>) {
+		if let Some(fees) = fees_then_tips.next() {
+			// 80% to the treasury, 20% burned.
+			let mut split = fees.ration(80, 20);
+			if let Some(tips) = fees_then_tips.next() {
+				tips.ration_merge_into(80, 20, &mut split);
+			}
+			Treasury::on_unbalanced(split.0);
+		}
+	}
 }
 
 impl pallet_transaction_payment::Config for Runtime {
-	type OnChargeTransaction = CurrencyAdapter<Balances, ()>;
+	/// Accounts that opted in pay in the fee asset, all others natively.
+	type OnChargeTransaction =
+		pallet_asset_fees::AssetFeeAdapter<Runtime, CurrencyAdapter<Balances, DealWithFees>>;
 	type TransactionByteFee = TransactionByteFee;
 	type WeightToFee = IdentityFee<Balance>;
-	type FeeMultiplierUpdate = ();
+	type FeeMultiplierUpdate =
+		TargetedFeeAdjustment<Self, TargetBlockFullness, AdjustmentVariable, MinimumMultiplier>;
 }
 
 impl pallet_sudo::Config for Runtime {
@@ -265,11 +331,442 @@ impl pallet_sudo::Config for Runtime {
 	type Call = Call;
 }
 
+parameter_types! {
+	pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) *
+		BlockWeights::get().max_block;
+	pub const MaxScheduledPerBlock: u32 = 50;
+}
+
+impl pallet_scheduler::Config for Runtime {
+	type Event = Event;
+	type Origin = Origin;
+	type PalletsOrigin = OriginCaller;
+	type Call = Call;
+	type MaximumWeight = MaximumSchedulerWeight;
+	type ScheduleOrigin = EnsureRoot<AccountId>;
+	type MaxScheduledPerBlock = MaxScheduledPerBlock;
+	type WeightInfo = pallet_scheduler::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const CouncilMotionDuration: BlockNumber = 3 * DAYS;
+	pub const CouncilMaxProposals: u32 = 100;
+	pub const CouncilMaxMembers: u32 = 100;
+}
+
+pub type CouncilCollective = pallet_collective::Instance1;
+
+impl pallet_collective::Config<CouncilCollective> for Runtime {
+	type Origin = Origin;
+	type Proposal = Call;
+	type Event = Event;
+	type MotionDuration = CouncilMotionDuration;
+	type MaxProposals = CouncilMaxProposals;
+	type MaxMembers = CouncilMaxMembers;
+	type DefaultVote = pallet_collective::PrimeDefaultVote;
+	type WeightInfo = pallet_collective::weights::SubstrateWeight<Runtime>;
+}
+
+/// Root, i.e. a passed referendum, or more than half of the council.
+pub type EnsureRootOrHalfCouncil = EnsureOneOf<
+	AccountId,
+	EnsureRoot<AccountId>,
+	pallet_collective::EnsureProportionMoreThan<_1, _2, AccountId, CouncilCollective>,
+>;
+
+parameter_types! {
+	pub const LaunchPeriod: BlockNumber = 7 * DAYS;
+	pub const VotingPeriod: BlockNumber = 7 * DAYS;
+	pub const FastTrackVotingPeriod: BlockNumber = 3 * HOURS;
+	pub const InstantAllowed: bool = true;
+	pub const MinimumDeposit: Balance = 50_000;
+	pub const EnactmentPeriod: BlockNumber = DAYS;
+	pub const CooloffPeriod: BlockNumber = 7 * DAYS;
+	pub const PreimageByteDeposit: Balance = 1;
+	pub const MaxVotes: u32 = 100;
+	pub const MaxProposals: u32 = 100;
+}
+
+impl pallet_democracy::Config for Runtime {
+	type Proposal = Call;
+	type Event = Event;
+	type Currency = Balances;
+	type EnactmentPeriod = EnactmentPeriod;
+	type LaunchPeriod = LaunchPeriod;
+	type VotingPeriod = VotingPeriod;
+	type MinimumDeposit = MinimumDeposit;
+	/// A straight majority of the council can decide what their next motion is.
+	type ExternalOrigin =
+		pallet_collective::EnsureProportionAtLeast<_1, _2, AccountId, CouncilCollective>;
+	/// A super-majority can have the next scheduled referendum be a straight majority-carries vote.
+	type ExternalMajorityOrigin =
+		pallet_collective::EnsureProportionAtLeast<_2, _3, AccountId, CouncilCollective>;
+	/// A unanimous council can have the next scheduled referendum be a straight default-carries
+	/// (NTB) vote.
+	type ExternalDefaultOrigin =
+		pallet_collective::EnsureProportionAtLeast<_1, _1, AccountId, CouncilCollective>;
+	/// Two thirds of the council can have an external proposal voted on more quickly.
+	type FastTrackOrigin =
+		pallet_collective::EnsureProportionAtLeast<_2, _3, AccountId, CouncilCollective>;
+	/// A unanimous council can have an external proposal voted on immediately.
+	type InstantOrigin =
+		pallet_collective::EnsureProportionAtLeast<_1, _1, AccountId, CouncilCollective>;
+	type InstantAllowed = InstantAllowed;
+	type FastTrackVotingPeriod = FastTrackVotingPeriod;
+	/// Two thirds of the council can cancel a passed referendum.
+	type CancellationOrigin =
+		pallet_collective::EnsureProportionAtLeast<_2, _3, AccountId, CouncilCollective>;
+	/// Root or a unanimous council can cancel a proposal before it becomes a referendum.
+	type CancelProposalOrigin = EnsureOneOf<
+		AccountId,
+		EnsureRoot<AccountId>,
+		pallet_collective::EnsureProportionAtLeast<_1, _1, AccountId, CouncilCollective>,
+	>;
+	type BlacklistOrigin = EnsureRoot<AccountId>;
+	/// Any single council member may veto a coming council proposal, however they can only do
+	/// it once and it lasts only for the cool-off period.
+	type VetoOrigin = pallet_collective::EnsureMember<AccountId, CouncilCollective>;
+	type CooloffPeriod = CooloffPeriod;
+	type PreimageByteDeposit = PreimageByteDeposit;
+	type OperationalPreimageOrigin = pallet_collective::EnsureMember<AccountId, CouncilCollective>;
+	type Slash = Treasury;
+	type Scheduler = Scheduler;
+	type PalletsOrigin = OriginCaller;
+	type MaxVotes = MaxVotes;
+	type WeightInfo = pallet_democracy::weights::SubstrateWeight<Runtime>;
+	type MaxProposals = MaxProposals;
+}
+
+parameter_types! {
+	pub const ProposalBond: Permill = Permill::from_percent(5);
+	pub const ProposalBondMinimum: Balance = 10_000;
+	pub const SpendPeriod: BlockNumber = DAYS;
+	pub const Burn: Permill = Permill::zero();
+	pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
+	pub const MaxApprovals: u32 = 100;
+}
+
+impl pallet_treasury::Config for Runtime {
+	type PalletId = TreasuryPalletId;
+	type Currency = Balances;
+	type ApproveOrigin = EnsureRootOrHalfCouncil;
+	type RejectOrigin = EnsureRootOrHalfCouncil;
+	type Event = Event;
+	type OnSlash = Treasury;
+	type ProposalBond = ProposalBond;
+	type ProposalBondMinimum = ProposalBondMinimum;
+	type SpendPeriod = SpendPeriod;
+	type Burn = Burn;
+	type BurnDestination = ();
+	type SpendFunds = ();
+	type WeightInfo = pallet_treasury::weights::SubstrateWeight<Runtime>;
+	type MaxApprovals = MaxApprovals;
+}
+
 /// Configure the pallet-template in pallets/template.
 impl pallet_template::Config for Runtime {
 	type Event = Event;
 }
 
+parameter_types! {
+	pub const ScriptDepositPerByte: Balance = 1;
+	pub const MaxScriptChunkLength: u32 = 64 * 1024;
+	pub const MaxScriptChunks: u32 = 64;
+	pub const MaxTokenUsersExpiringPerBlock: u32 = 64;
+}
+
+/// Configure the pallet-generative in pallets/generative.
+impl pallet_generative::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type DepositPerByte = ScriptDepositPerByte;
+	type MaxChunkLength = MaxScriptChunkLength;
+	type MaxChunks = MaxScriptChunks;
+	type FeatureOrigin =
+		EnsureOneOf<AccountId, EnsureRoot<AccountId>, pallet_curators::EnsureCurators>;
+	type MaxUsersExpiringPerBlock = MaxTokenUsersExpiringPerBlock;
+}
+
+parameter_types! {
+	pub const MaxSubscriptionTiers: u32 = 16;
+	pub const MinSubscriptionPeriod: BlockNumber = DAYS;
+	pub const MaxSubscriptionChargesPerBlock: u32 = 64;
+}
+
+/// Configure the pallet-subscriptions in pallets/subscriptions.
+impl pallet_subscriptions::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type MaxTiers = MaxSubscriptionTiers;
+	type MinPeriod = MinSubscriptionPeriod;
+	type MaxChargesPerBlock = MaxSubscriptionChargesPerBlock;
+	type WeightInfo = pallet_subscriptions::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const MinCampaignDuration: BlockNumber = HOURS;
+	pub const MaxCampaignDuration: BlockNumber = 90 * DAYS;
+	pub const MaxCampaignRewardTiers: u32 = 8;
+	pub const MaxCampaignSettlementsPerBlock: u32 = 64;
+	pub const MaxCampaignsEndingPerBlock: u32 = 64;
+}
+
+/// Mints crowdfunding rewards as tokens of the campaign creator's generative collections.
+pub struct GenerativeRewards;
+
+impl pallet_crowdfunding::RewardMinter<AccountId> for GenerativeRewards {
+	type Reward = pallet_generative::CollectionId;
+
+	fn can_mint(creator: &AccountId, collection: &Self::Reward) -> bool {
+		Generative::collection(collection)
+			.map_or(false, |details| &details.creator == creator && details.chunks > 0)
+	}
+
+	fn freeze(collection: &Self::Reward) {
+		Generative::freeze_script(*collection)
+	}
+
+	fn mint(
+		creator: &AccountId,
+		collection: &Self::Reward,
+		owner: &AccountId,
+	) -> sp_runtime::DispatchResult {
+		Generative::do_mint(*collection, creator, owner.clone())
+	}
+}
+
+/// Configure the pallet-crowdfunding in pallets/crowdfunding.
+impl pallet_crowdfunding::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type Rewards = GenerativeRewards;
+	type MinDuration = MinCampaignDuration;
+	type MaxDuration = MaxCampaignDuration;
+	type MaxRewardTiers = MaxCampaignRewardTiers;
+	type MaxSettlementsPerBlock = MaxCampaignSettlementsPerBlock;
+	type MaxCampaignsEndingPerBlock = MaxCampaignsEndingPerBlock;
+}
+
+/// Artworks are identified by the collection and id of a generative token.
+pub type ArtworkId = (pallet_generative::CollectionId, pallet_generative::TokenId);
+
+/// Looks up the creator of a generative token for tipping.
+pub struct GenerativeCreators;
+
+impl pallet_tipping::ArtworkCreators<ArtworkId, AccountId> for GenerativeCreators {
+	fn creators((collection, token): &ArtworkId) -> Option<Vec<AccountId>> {
+		Generative::token(collection, token)?;
+		Generative::collection(collection).map(|details| vec![details.creator])
+	}
+}
+
+parameter_types! {
+	/// The platform share of tips goes to the treasury.
+	pub PlatformAccount: AccountId = Treasury::account_id();
+	pub const TipPlatformShare: Perbill = Perbill::from_percent(5);
+	pub const MaxTipMessageLength: u32 = 140;
+}
+
+/// Configure the pallet-tipping in pallets/tipping.
+impl pallet_tipping::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type ArtworkId = ArtworkId;
+	type Creators = GenerativeCreators;
+	type PlatformAccount = PlatformAccount;
+	type PlatformShare = TipPlatformShare;
+	type CharityOrigin = EnsureRootOrHalfCouncil;
+	type MaxMessageLength = MaxTipMessageLength;
+}
+
+/// Configure the pallet-badges in pallets/badges.
+impl pallet_badges::Config for Runtime {
+	type Event = Event;
+	type IssuerOrigin = EnsureRootOrHalfCouncil;
+}
+
+/// Hands out an attendance badge, issued by the organizer, for every checked in ticket. Only
+/// organizers authorized as badge issuers hand out badges; anyone can organize an exhibition.
+pub struct AttendanceBadges;
+
+impl pallet_ticketing::OnCheckIn<AccountId> for AttendanceBadges {
+	fn on_check_in(
+		exhibition: pallet_ticketing::ExhibitionId,
+		organizer: &AccountId,
+		attendee: &AccountId,
+	) {
+		if !pallet_badges::Issuers::<Runtime>::contains_key(organizer) {
+			return
+		}
+		let metadata = <BlakeTwo256 as sp_runtime::traits::Hash>::hash_of(&exhibition);
+		// Running out of badge ids must not keep attendees from entering.
+		let _ = Badges::do_issue(
+			organizer.clone(),
+			attendee.clone(),
+			pallet_badges::BadgeKind::Attendance,
+			metadata,
+		);
+	}
+
+	fn on_check_in_weight() -> Weight {
+		// The issuer check and `Badges::do_issue`.
+		RocksDbWeight::get().reads_writes(2, 3)
+	}
+}
+
+parameter_types! {
+	pub const MaxTicketPriceTiers: u32 = 8;
+}
+
+/// Configure the pallet-ticketing in pallets/ticketing.
+impl pallet_ticketing::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type OnCheckIn = AttendanceBadges;
+	type MaxPriceTiers = MaxTicketPriceTiers;
+}
+
+parameter_types! {
+	pub const CuratorMinStake: Balance = 10_000;
+	pub const MaxCurators: u32 = 256;
+	pub const CuratorVotingPeriod: BlockNumber = 7 * DAYS;
+	pub const CuratorApprovalThreshold: Perbill = Perbill::from_percent(50);
+	pub const CuratorProposalDepositBase: Balance = 1_000;
+	pub const CuratorProposalDepositPerByte: Balance = 1;
+	pub const CuratorsPalletId: PalletId = PalletId(*b"bca/crtr");
+}
+
+/// Configure the pallet-curators in pallets/curators.
+impl pallet_curators::Config for Runtime {
+	type Event = Event;
+	type Origin = Origin;
+	type Proposal = Call;
+	type Currency = Balances;
+	type GrantOrigin = pallet_curators::EnsureCurators;
+	type MinStake = CuratorMinStake;
+	type MaxMembers = MaxCurators;
+	type VotingPeriod = CuratorVotingPeriod;
+	type ApprovalThreshold = CuratorApprovalThreshold;
+	type ProposalDepositBase = CuratorProposalDepositBase;
+	type ProposalDepositPerByte = CuratorProposalDepositPerByte;
+	type PalletId = CuratorsPalletId;
+}
+
+/// The calls verified artists may submit without fees: creating, scripting and minting
+/// generative collections.
+pub struct ArtistFreeCalls;
+
+impl frame_support::traits::Filter<Call> for ArtistFreeCalls {
+	fn filter(call: &Call) -> bool {
+		matches!(
+			call,
+			Call::Generative(pallet_generative::Call::create_collection(..)) |
+				Call::Generative(pallet_generative::Call::append_script_chunk(..)) |
+				Call::Generative(pallet_generative::Call::mint(..))
+		)
+	}
+}
+
+parameter_types! {
+	pub const ArtistQuotaPeriod: BlockNumber = DAYS;
+	pub const ArtistFreeCallsPerPeriod: u32 = 20;
+}
+
+/// Configure the pallet-artists in pallets/artists.
+impl pallet_artists::Config for Runtime {
+	type Event = Event;
+	type VerifierOrigin = EnsureRootOrHalfCouncil;
+	type FreeCalls = ArtistFreeCalls;
+	type QuotaPeriod = ArtistQuotaPeriod;
+	type FreeCallsPerPeriod = ArtistFreeCallsPerPeriod;
+}
+
+parameter_types! {
+	pub const MaxSponsoredCalls: u32 = 16;
+}
+
+/// Configure the pallet-sponsorship in pallets/sponsorship.
+impl pallet_sponsorship::Config for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type OffchainSignature = Signature;
+	type Signer = <Signature as Verify>::Signer;
+	type MaxPolicyCalls = MaxSponsoredCalls;
+}
+
+parameter_types! {
+	pub const AssetDeposit: Balance = 100_000;
+	pub const ApprovalDeposit: Balance = 500;
+	pub const AssetsStringLimit: u32 = 50;
+	pub const MetadataDepositBase: Balance = 10_000;
+	pub const MetadataDepositPerByte: Balance = 1_000;
+}
+
+impl pallet_assets::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = u32;
+	type Currency = Balances;
+	type ForceOrigin = EnsureRootOrHalfCouncil;
+	type AssetDeposit = AssetDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type ApprovalDeposit = ApprovalDeposit;
+	type StringLimit = AssetsStringLimit;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub AssetFeeCollector: AccountId = Treasury::account_id();
+}
+
+/// Configure the pallet-asset-fees in pallets/asset-fees.
+impl pallet_asset_fees::Config for Runtime {
+	type Event = Event;
+	type Assets = Assets;
+	type Sufficiency =
+		pallet_asset_fees::PalletAssetsSufficiency<Assets, AccountId, Balance, Balance>;
+	type FeeAssetOrigin = EnsureRootOrHalfCouncil;
+	type FeeCollector = AssetFeeCollector;
+}
+
+/// The calls that can never be paused: system and consensus calls, governance, including the
+/// treasury, artist verification and the curators, and the pause pallet itself.
+pub struct Unpausable;
+
+impl frame_support::traits::Filter<Call> for Unpausable {
+	fn filter(call: &Call) -> bool {
+		matches!(
+			call,
+			Call::System(_) |
+				Call::Timestamp(_) |
+				Call::Grandpa(_) |
+				Call::Sudo(_) |
+				Call::Scheduler(_) |
+				Call::Council(_) |
+				Call::Democracy(_) |
+				Call::Treasury(_) |
+				Call::Artists(_) |
+				Call::Curators(_) |
+				Call::Pause(_)
+		)
+	}
+}
+
+parameter_types! {
+	pub const MaxPauseNameLength: u32 = 64;
+}
+
+/// Configure the pallet-pause in pallets/pause.
+impl pallet_pause::Config for Runtime {
+	type Event = Event;
+	type PauseOrigin = EnsureRootOrHalfCouncil;
+	type Unpausable = Unpausable;
+	type MaxNameLength = MaxPauseNameLength;
+}
+
 // Create the runtime by composing the FRAME pallets that were previously configured.
 construct_runtime!(
 	pub enum Runtime where
@@ -287,6 +784,22 @@ construct_runtime!(
 		Sudo: pallet_sudo::{Pallet, Call, Config<T>, Storage, Event<T>},
 		// Include the custom logic from the pallet-template in the runtime.
 		TemplateModule: pallet_template::{Pallet, Call, Storage, Event<T>},
+		Generative: pallet_generative::{Pallet, Call, Storage, Event<T>},
+		Subscriptions: pallet_subscriptions::{Pallet, Call, Storage, Event<T>},
+		Crowdfunding: pallet_crowdfunding::{Pallet, Call, Storage, Event<T>},
+		Tipping: pallet_tipping::{Pallet, Call, Storage, Event<T>},
+		Badges: pallet_badges::{Pallet, Call, Storage, Event<T>},
+		Ticketing: pallet_ticketing::{Pallet, Call, Storage, Event<T>},
+		Scheduler: pallet_scheduler::{Pallet, Call, Storage, Event<T>},
+		Council: pallet_collective::<Instance1>::{Pallet, Call, Storage, Origin<T>, Event<T>, Config<T>},
+		Democracy: pallet_democracy::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Curators: pallet_curators::{Pallet, Call, Storage, Origin, Event<T>},
+		Treasury: pallet_treasury::{Pallet, Call, Storage, Config, Event<T>},
+		Artists: pallet_artists::{Pallet, Call, Storage, Event<T>},
+		Sponsorship: pallet_sponsorship::{Pallet, Call, Storage, Event<T>},
+		Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
+		AssetFees: pallet_asset_fees::{Pallet, Call, Storage, Event<T>},
+		Pause: pallet_pause::{Pallet, Call, Storage, Event<T>},
 	}
 );
 
@@ -304,10 +817,28 @@ pub type SignedExtra = (
 	frame_system::CheckEra<Runtime>,
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
-	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	pallet_artists::WaiveFees<
+		Runtime,
+		pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	>,
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
+
+/// Removes the sudo key from storage.
+///
+/// `Sudo` stays in the runtime until the council and democracy have taken over. The runtime
+/// upgrade retiring it drops `Sudo` from `construct_runtime!` and passes this migration as the
+/// last parameter of `Executive`, so that the old key does not linger in storage.
+pub struct RemoveSudo;
+
+impl frame_support::traits::OnRuntimeUpgrade for RemoveSudo {
+	fn on_runtime_upgrade() -> Weight {
+		frame_support::storage::migration::remove_storage_prefix(b"Sudo", b"Key", &[]);
+		RocksDbWeight::get().writes(1)
+	}
+}
+
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -317,6 +848,14 @@ pub type Executive = frame_executive::Executive<
 	AllPallets,
 >;
 
+sp_api::decl_runtime_apis! {
+	/// The API to query the fee multiplier of the transaction payment pallet.
+	pub trait FeeMultiplierApi {
+		/// The multiplier applied to the weight fee of transactions in the next block.
+		fn next_fee_multiplier() -> Multiplier;
+	}
+}
+
 impl_runtime_apis! {
 	impl sp_api::Core<Block> for Runtime {
 		fn version() -> RuntimeVersion {
@@ -444,6 +983,38 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl FeeMultiplierApi<Block> for Runtime {
+		fn next_fee_multiplier() -> Multiplier {
+			TransactionPayment::next_fee_multiplier()
+		}
+	}
+
+	impl pallet_generative_runtime_api::GenerativeApi<Block, AccountId, Hash> for Runtime {
+		fn generator(
+			collection: pallet_generative::CollectionId,
+			token: pallet_generative::TokenId,
+		) -> Option<pallet_generative::GenerativeArtwork<Hash>> {
+			Generative::generator(collection, token)
+		}
+
+		fn display_rights(
+			collection: pallet_generative::CollectionId,
+			token: pallet_generative::TokenId,
+		) -> Option<AccountId> {
+			Generative::display_rights(collection, token)
+		}
+	}
+
+	#[cfg(feature = "try-runtime")]
+	impl frame_try_runtime::TryRuntime<Block> for Runtime {
+		fn on_runtime_upgrade() -> Result<(Weight, Weight), sp_runtime::RuntimeString> {
+			// Runs the `pre_upgrade` and `post_upgrade` checks of all pallets around the
+			// migrations.
+			let weight = Executive::try_runtime_upgrade()?;
+			Ok((weight, BlockWeights::get().max_block))
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn dispatch_benchmark(
@@ -480,9 +1051,24 @@ impl_runtime_apis! {
 			add_benchmark!(params, batches, pallet_balances, Balances);
 			add_benchmark!(params, batches, pallet_timestamp, Timestamp);
 			add_benchmark!(params, batches, pallet_template, TemplateModule);
+			add_benchmark!(params, batches, pallet_generative, Generative);
+			add_benchmark!(params, batches, pallet_subscriptions, Subscriptions);
+			add_benchmark!(params, batches, pallet_crowdfunding, Crowdfunding);
+			add_benchmark!(params, batches, pallet_badges, Badges);
+			add_benchmark!(params, batches, pallet_ticketing, Ticketing);
+			add_benchmark!(params, batches, pallet_scheduler, Scheduler);
+			add_benchmark!(params, batches, pallet_collective, Council);
+			add_benchmark!(params, batches, pallet_democracy, Democracy);
+			add_benchmark!(params, batches, pallet_curators, Curators);
+			add_benchmark!(params, batches, pallet_treasury, Treasury);
+			add_benchmark!(params, batches, pallet_artists, Artists);
+			add_benchmark!(params, batches, pallet_assets, Assets);
 
 			if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
 			Ok((batches, storage_info))
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests;
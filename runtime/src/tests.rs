@@ -0,0 +1,29 @@
+use super::*;
+use frame_support::assert_ok;
+use pallet_ticketing::OnCheckIn;
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	let storage = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+#[test]
+fn only_authorized_organizers_hand_out_attendance_badges() {
+	new_test_ext().execute_with(|| {
+		let organizer = AccountId::from([1; 32]);
+		let attendee = AccountId::from([2; 32]);
+
+		AttendanceBadges::on_check_in(0, &organizer, &attendee);
+		assert!(Badges::badges_of(&attendee).is_empty());
+
+		assert_ok!(Badges::add_issuer(Origin::root(), organizer.clone().into()));
+		AttendanceBadges::on_check_in(0, &organizer, &attendee);
+		let badges = Badges::badges_of(&attendee);
+		assert_eq!(badges.len(), 1);
+		let badge = Badges::badge(badges[0]).unwrap();
+		assert_eq!(badge.issuer, organizer);
+		assert_eq!(badge.kind, pallet_badges::BadgeKind::Attendance);
+	});
+}